@@ -0,0 +1,245 @@
+//! Benchmarks parsing and both parts of every registered solution on its real input.
+//!
+//! `cargo bench [-- [YEAR [DAY]] [--baseline NAME | --save-baseline NAME]]`
+//!
+//! The median of each phase is compared with a baseline stored below `target`, and a warning is
+//! printed for every phase that became measurably slower. Without options the run is compared
+//! with the previous one and then becomes the new baseline. `--save-baseline` does the same for a
+//! named baseline, while `--baseline` only compares with it.
+
+use aoc::inputs::Inputs;
+use aoc::runner::{self, format_duration, Summary};
+use aoc::{solutions, Solution};
+use std::collections::BTreeMap;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Minimum duration of one sample, fast phases are repeated to reach it.
+const SAMPLE_TIME: Duration = Duration::from_millis(5);
+const SAMPLES: usize = 20;
+/// Fewer samples are taken of phases that would take longer than this in total.
+const PHASE_TIME: Duration = Duration::from_secs(3);
+const MIN_SAMPLES: usize = 3;
+/// Slowdown of the median, relative to the baseline, that counts as a regression.
+const THRESHOLD: f64 = 0.10;
+
+const DEFAULT_BASELINE: &str = "previous";
+
+/// Median nanoseconds per phase, keyed by year, day and phase name.
+type Baseline = BTreeMap<(u32, u32, String), u128>;
+
+struct Options {
+    year: Option<u32>,
+    day: Option<u32>,
+    baseline: String,
+    save: bool,
+}
+
+fn main() {
+    let options = match parse_options(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            std::process::exit(2);
+        }
+    };
+    let inputs = Inputs::from_env();
+    let path = baseline_path(&options.baseline);
+    let previous = match load(&path) {
+        Ok(previous) => previous,
+        Err(error) => {
+            eprintln!("Cannot read {}: {}", path.display(), error);
+            std::process::exit(1);
+        }
+    };
+    let mut current = previous.clone();
+    let mut regressions = 0;
+
+    let selected = solutions().into_iter().filter(|solution| {
+        options.year.is_none_or(|year| year == solution.year)
+            && options.day.is_none_or(|day| day == solution.day)
+    });
+    for solution in selected {
+        let name = format!("{} {:02}", solution.year, solution.day);
+        let data = match inputs.read(solution.year, solution.day) {
+            Ok(data) => data,
+            Err(error) => {
+                println!("{}  skipped: {}", name, runner::Error::Input(error));
+                continue;
+            }
+        };
+        for (phase, summary) in bench(&solution, &data) {
+            let key = (solution.year, solution.day, phase.to_string());
+            let median = summary.median.as_nanos();
+            let change = previous
+                .get(&key)
+                .map(|before| (median as f64 - *before as f64) / *before as f64);
+            println!(
+                "{}  {:<5}  {:>10}  ({} … {}){}",
+                name,
+                phase,
+                format_duration(summary.median),
+                format_duration(summary.min),
+                format_duration(summary.max),
+                change.map_or(String::new(), |change| format!("  {:+.1}%", change * 100.0))
+            );
+            if change.is_some_and(|change| change > THRESHOLD) {
+                let before = Duration::from_nanos(previous[&key] as u64);
+                // Only the fastest sample being slower than the old median rules out noise.
+                if summary.min > before {
+                    regressions += 1;
+                    println!(
+                        "warning: {} {} is slower than baseline '{}': {} -> {}",
+                        name,
+                        phase,
+                        options.baseline,
+                        format_duration(before),
+                        format_duration(summary.median)
+                    );
+                }
+            }
+            current.insert(key, median);
+        }
+    }
+
+    if regressions > 0 {
+        println!("\n{} phases became slower", regressions);
+    }
+    if options.save {
+        if let Err(error) = save(&path, &current) {
+            eprintln!("Cannot write {}: {}", path.display(), error);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        year: None,
+        day: None,
+        baseline: DEFAULT_BASELINE.to_string(),
+        save: true,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // Passed by `cargo bench` to every benchmark target.
+            "--bench" => {}
+            "--baseline" | "--save-baseline" => {
+                options.save = arg == "--save-baseline";
+                options.baseline = args
+                    .next()
+                    .ok_or_else(|| format!("option '{}' needs a value", arg))?;
+            }
+            number if options.year.is_none() => {
+                options.year = Some(number.parse().map_err(|_| invalid(&arg))?)
+            }
+            number if options.day.is_none() => {
+                options.day = Some(number.parse().map_err(|_| invalid(&arg))?)
+            }
+            _ => return Err(invalid(&arg)),
+        }
+    }
+    Ok(options)
+}
+
+fn invalid(argument: &str) -> String {
+    format!("unexpected argument '{}'", argument)
+}
+
+/// Times the phases of a solution, each on its own.
+fn bench(solution: &Solution, data: &str) -> Vec<(&'static str, Summary)> {
+    let mut phases = vec![("parse", measure(|| drop(black_box(solution.parse(data)))))];
+    match solution.parse(data) {
+        Ok(input) => {
+            let input = input.as_ref();
+            phases.push(("part1", measure(|| drop(black_box(solution.part1(input))))));
+            if solution.parts > 1 {
+                phases.push(("part2", measure(|| drop(black_box(solution.part2(input))))));
+            }
+        }
+        Err(error) => println!(
+            "{} {:02}  invalid input: {}",
+            solution.year, solution.day, error
+        ),
+    }
+    phases
+}
+
+fn measure(mut phase: impl FnMut()) -> Summary {
+    // Doubles the iterations per sample until a sample takes long enough to time reliably,
+    // which also warms up caches and branch predictors.
+    let mut iterations = 1u32;
+    let mut elapsed = time(&mut phase, iterations);
+    while elapsed < SAMPLE_TIME {
+        iterations *= 2;
+        elapsed = time(&mut phase, iterations);
+    }
+    let samples = (PHASE_TIME.as_nanos() / elapsed.as_nanos().max(1)) as usize;
+    let mut samples = (0..samples.clamp(MIN_SAMPLES, SAMPLES))
+        .map(|_| time(&mut phase, iterations) / iterations)
+        .collect::<Vec<_>>();
+    Summary::of(&mut samples)
+}
+
+fn time(phase: &mut impl FnMut(), iterations: u32) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        phase();
+    }
+    start.elapsed()
+}
+
+fn baseline_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("aoc-bench")
+        .join(format!("{}.txt", name))
+}
+
+/// Reads a baseline with one `<year> <day> <phase> <nanoseconds>` line per phase.
+fn load(path: &Path) -> io::Result<Baseline> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Baseline::new()),
+        Err(error) => return Err(error),
+    };
+    content
+        .lines()
+        .enumerate()
+        .map(|(number, line)| {
+            parse_line(line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "line {}: expected '<year> <day> <phase> <nanoseconds>'",
+                        number + 1
+                    ),
+                )
+            })
+        })
+        .collect()
+}
+
+fn parse_line(line: &str) -> Option<((u32, u32, String), u128)> {
+    match line.split_whitespace().collect::<Vec<_>>()[..] {
+        [year, day, phase, nanos] => Some((
+            (year.parse().ok()?, day.parse().ok()?, phase.to_string()),
+            nanos.parse().ok()?,
+        )),
+        _ => None,
+    }
+}
+
+fn save(path: &Path, baseline: &Baseline) -> io::Result<()> {
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    let content = baseline
+        .iter()
+        .map(|((year, day, phase), nanos)| format!("{} {:02} {} {}\n", year, day, phase, nanos))
+        .collect::<String>();
+    fs::write(path, content)
+}
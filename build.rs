@@ -0,0 +1,75 @@
+//! Declares a module for every `src/year_<year>/day<day>.rs` and registers its solution.
+//!
+//! Also fills in the day template the way `aoc new` does, so the tests compile a new day.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Fills in the template exactly like `aoc new`; the rest of the module is not needed here.
+#[allow(dead_code)]
+#[path = "src/bin/aoc/scaffold.rs"]
+mod scaffold;
+
+fn main() {
+    let source =
+        Path::new(&env::var("CARGO_MANIFEST_DIR").expect("manifest directory")).join("src");
+    println!("cargo::rerun-if-changed={}", source.display());
+
+    let mut generated = String::new();
+    let mut registry = String::new();
+    for (year, directory) in numbered_entries(&source, "year_", "") {
+        let days = numbered_entries(&directory, "day", ".rs");
+        if days.is_empty() {
+            continue;
+        }
+        writeln!(generated, "pub mod year_{} {{", year).unwrap();
+        write!(registry, "        year_{}:", year).unwrap();
+        for (index, (day, path)) in days.iter().enumerate() {
+            writeln!(generated, "    #[path = {:?}]", path.display().to_string()).unwrap();
+            writeln!(generated, "    pub mod day{:02};", day).unwrap();
+            let separator = if index == 0 { " " } else { ", " };
+            write!(registry, "{}day{:02}", separator, day).unwrap();
+        }
+        writeln!(generated, "}}\n").unwrap();
+        writeln!(registry, ";").unwrap();
+    }
+    writeln!(
+        generated,
+        "/// All registered solutions, ordered by year and day.\n\
+         pub fn solutions() -> Vec<Solution> {{\n    solutions!(\n{}    )\n}}",
+        registry
+    )
+    .unwrap();
+
+    let out = PathBuf::from(env::var("OUT_DIR").expect("output directory"));
+    fs::write(out.join("solutions.rs"), generated).expect("writable output directory");
+
+    let template = fs::read_to_string(source.join("template.rs")).expect("readable template");
+    let instantiated = scaffold::instantiate(&template, TEMPLATE_YEAR, TEMPLATE_DAY);
+    fs::write(out.join("template.rs"), instantiated).expect("writable output directory");
+}
+
+/// The day the template is compiled as in the tests.
+const TEMPLATE_YEAR: u32 = 2015;
+const TEMPLATE_DAY: u32 = 1;
+
+/// Entries named `<prefix><number><suffix>`, ordered by their number.
+fn numbered_entries(directory: &Path, prefix: &str, suffix: &str) -> Vec<(u32, PathBuf)> {
+    let mut entries = fs::read_dir(directory)
+        .expect("readable source directory")
+        .filter_map(|entry| {
+            let path = entry.expect("readable directory entry").path();
+            let name = path.file_name()?.to_str()?;
+            let number = name
+                .strip_prefix(prefix)?
+                .strip_suffix(suffix)?
+                .parse()
+                .ok()?;
+            Some((number, path))
+        })
+        .collect::<Vec<_>>();
+    entries.sort();
+    entries
+}
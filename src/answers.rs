@@ -0,0 +1,254 @@
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Write as _};
+use std::fs::{read_to_string, OpenOptions};
+use std::io;
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+
+/// Default file with the known answers, next to the inputs they belong to.
+pub const ANSWERS: &str = "answers.txt";
+
+/// What a part shows when it has no answer, like the second part of the last day of a year.
+pub const NO_ANSWER: &str = "-";
+
+/// Known answers keyed by year, day and part.
+///
+/// Stored as one answer per line: `<year> <day> <part> <answer>`.
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Default, PartialEq)]
+pub struct KnownAnswers {
+    answers: BTreeMap<(u32, u32, u8), String>,
+    /// Answers recorded since loading, which [`KnownAnswers::save`] appends to the file.
+    recorded: Vec<(u32, u32, u8)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl KnownAnswers {
+    /// Loads the answers from a file, a missing file has no answers.
+    pub fn load(path: &Path) -> io::Result<KnownAnswers> {
+        match read_to_string(path) {
+            Ok(content) => content
+                .parse()
+                .map_err(|error| io::Error::new(ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(KnownAnswers::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Appends the answers recorded since loading to the file, keeping the lines already in it.
+    pub fn save(&mut self, path: &Path) -> io::Result<()> {
+        if self.recorded.is_empty() {
+            return Ok(());
+        }
+        let mut lines = match read_to_string(path) {
+            Ok(content) if !content.is_empty() && !content.ends_with('\n') => "\n".to_string(),
+            Ok(_) => String::new(),
+            Err(error) if error.kind() == ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error),
+        };
+        for key in &self.recorded {
+            writeln!(lines, "{}", line(*key, &self.answers[key])).expect("writing to a string");
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(lines.as_bytes())?;
+        self.recorded.clear();
+        Ok(())
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: u8) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(String::as_str)
+    }
+
+    pub fn contains(&self, year: u32, day: u32) -> bool {
+        self.answers
+            .range((year, day, 0)..=(year, day, u8::MAX))
+            .next()
+            .is_some()
+    }
+
+    /// Records an answer unless one is already known, returns whether it was added.
+    ///
+    /// Fails for answers that would not read back the same from their line of the file.
+    pub fn record(&mut self, year: u32, day: u32, part: u8, answer: &str) -> Result<bool, String> {
+        if answer.is_empty() || answer == NO_ANSWER {
+            return Err("there is no answer".to_string());
+        }
+        if answer.contains(['\n', '\r']) {
+            return Err("the answer spans several lines".to_string());
+        }
+        if answer.trim() != answer {
+            return Err("the answer starts or ends with whitespace".to_string());
+        }
+        match self.answers.entry((year, day, part)) {
+            Entry::Occupied(_) => Ok(false),
+            Entry::Vacant(entry) => {
+                entry.insert(answer.to_string());
+                self.recorded.push((year, day, part));
+                Ok(true)
+            }
+        }
+    }
+
+    pub fn check(&self, year: u32, day: u32, part: u8, answer: &str) -> Status {
+        match self.get(year, day, part) {
+            None => Status::Missing,
+            Some(expected) if expected == answer => Status::Pass,
+            Some(expected) => Status::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+impl std::str::FromStr for KnownAnswers {
+    type Err = String;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let mut answers = BTreeMap::new();
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = line.splitn(4, char::is_whitespace).collect::<Vec<_>>();
+            if let [year, day, part, answer] = fields[..] {
+                let key = (
+                    year.parse().map_err(|_| invalid(number, "year"))?,
+                    day.parse().map_err(|_| invalid(number, "day"))?,
+                    part.parse().map_err(|_| invalid(number, "part"))?,
+                );
+                answers.insert(key, answer.trim().to_string());
+            } else {
+                return Err(invalid(number, "answer line"));
+            }
+        }
+        Ok(KnownAnswers {
+            answers,
+            recorded: Vec::new(),
+        })
+    }
+}
+
+/// An answer as a line of the file.
+fn line((year, day, part): (u32, u32, u8), answer: &str) -> String {
+    format!("{} {:02} {} {}", year, day, part, answer)
+}
+
+fn invalid(number: usize, what: &str) -> String {
+    format!("line {}: invalid {}", number + 1, what)
+}
+
+impl Display for KnownAnswers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (key, answer) in &self.answers {
+            writeln!(f, "{}", line(*key, answer))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            Status::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "\
+# year day part answer
+2015 01 1 138
+2015 01 2 1771
+
+2024 05 1 4578
+";
+
+    #[test]
+    fn parses_answers() {
+        let answers = EXAMPLE.parse::<KnownAnswers>().unwrap();
+        assert_eq!(answers.get(2015, 1, 2), Some("1771"));
+        assert_eq!(answers.get(2024, 5, 1), Some("4578"));
+        assert_eq!(answers.get(2024, 5, 2), None);
+        assert!(answers.contains(2024, 5));
+        assert!(!answers.contains(2024, 6));
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        assert_eq!(
+            "2015 01 1 138\n2015 x 2 1771".parse::<KnownAnswers>(),
+            Err("line 2: invalid day".to_string())
+        );
+    }
+
+    #[test]
+    fn checks_answers() {
+        let answers = EXAMPLE.parse::<KnownAnswers>().unwrap();
+        assert_eq!(answers.check(2015, 1, 1, "138"), Status::Pass);
+        assert_eq!(
+            answers.check(2015, 1, 2, "1772"),
+            Status::Fail {
+                expected: "1771".into()
+            }
+        );
+        assert_eq!(answers.check(2024, 5, 2, "6"), Status::Missing);
+    }
+
+    #[test]
+    fn records_only_new_answers() {
+        let mut answers = EXAMPLE.parse::<KnownAnswers>().unwrap();
+        assert_eq!(answers.record(2015, 1, 1, "139"), Ok(false));
+        assert_eq!(answers.record(2024, 5, 2, "6"), Ok(true));
+        assert_eq!(
+            answers.to_string(),
+            "2015 01 1 138\n2015 01 2 1771\n2024 05 1 4578\n2024 05 2 6\n"
+        );
+    }
+
+    #[rstest]
+    #[case("", "there is no answer")]
+    #[case("-", "there is no answer")]
+    #[case("#..#\n.##.", "the answer spans several lines")]
+    #[case("42\r", "the answer spans several lines")]
+    #[case(" 42", "the answer starts or ends with whitespace")]
+    fn refuses_unreadable_answers(#[case] answer: &str, #[case] error: &str) {
+        let mut answers = KnownAnswers::default();
+        assert_eq!(answers.record(2024, 5, 1, answer), Err(error.to_string()));
+        assert_eq!(answers.get(2024, 5, 1), None);
+    }
+
+    #[test]
+    fn appends_recorded_answers() {
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.txt", std::process::id()));
+        std::fs::write(&path, EXAMPLE.trim_end()).unwrap();
+        let mut answers = KnownAnswers::load(&path).unwrap();
+        answers.record(2024, 5, 2, "6").unwrap();
+        answers.record(2015, 2, 1, "1606483").unwrap();
+        answers.save(&path).unwrap();
+        answers.save(&path).unwrap();
+        let content = read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            content,
+            format!("{}2024 05 2 6\n2015 02 1 1606483\n", EXAMPLE)
+        );
+        assert_eq!(content.parse(), Ok(answers));
+    }
+}
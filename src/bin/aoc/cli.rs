@@ -0,0 +1,467 @@
+use aoc::inputs::Inputs;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: aoc <COMMAND> [OPTIONS]
+
+Commands:
+  run [YEAR [DAY [FILE]]]
+                      Run the selected solutions and print their answers, FILE as for --input
+  bench [YEAR [DAY]]  Run the selected solutions repeatedly and print timings
+  check [YEAR [DAY]]  Compare the answers with the known answers
+  list [YEAR]         List the implemented days with their inputs and known answers
+  new YEAR DAY        Create a new day from the template
+  visualise YEAR DAY  Draw how the solution works, for days that support it
+  import YEAR DAY FILE
+                      Store FILE as the input of the day and record its checksum
+  verify [YEAR [DAY]] Check the stored inputs against their checksums, of all accounts by default
+
+Options:
+  --year YEAR         Select a year, same as the positional YEAR
+  --day DAY           Select a day, same as the positional DAY
+  --input FILE        Read the input from FILE instead of the inputs directory, '-' for stdin
+  --inputs DIR        Directory with the inputs [env: AOC_INPUTS] [default: inputs]
+  --account NAME      Use the inputs and answers of another account, kept in DIR/accounts/NAME
+  --repeat N          Number of runs to time [default: 1, bench: 10]
+  --check             Compare the answers with the known answers (run)
+  --record            Store answers that are not known yet (run, check)
+  --format FORMAT     Output as a 'table', or as 'json' or 'csv' with timings in nanoseconds
+                      [default: table]
+  --frames DIR        Also write every picture as a PPM image into DIR (visualise)
+  --scale N           Pixels per grid cell in the images [default: 4]
+  -h, --help          Print this help
+";
+
+const BENCH_REPEAT: usize = 10;
+const FRAME_SCALE: usize = 4;
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    List(Selection, Inputs),
+    New {
+        year: u32,
+        day: u32,
+    },
+    Visualise(VisualiseOptions),
+    Import {
+        year: u32,
+        day: u32,
+        file: PathBuf,
+        inputs: Inputs,
+    },
+    /// Verifies the inputs of one account, or of all accounts if none is given.
+    Verify {
+        selection: Selection,
+        inputs: Inputs,
+        account: Option<String>,
+    },
+    Help,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Selection {
+    pub year: Option<u32>,
+    pub day: Option<u32>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    pub selection: Selection,
+    pub inputs: Inputs,
+    pub file: Option<PathBuf>,
+    pub repeat: usize,
+    pub spread: bool,
+    pub check: bool,
+    pub record: bool,
+    pub format: Format,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VisualiseOptions {
+    pub year: u32,
+    pub day: u32,
+    pub inputs: Inputs,
+    pub file: Option<PathBuf>,
+    pub frames: Option<PathBuf>,
+    pub scale: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    UnknownCommand(String),
+    UnknownOption(String),
+    MissingValue(String),
+    InvalidYear(String),
+    InvalidDay(String),
+    InvalidValue { option: String, value: String },
+    UnexpectedArgument(String),
+    Missing(&'static str),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnknownCommand(command) => write!(f, "unknown command '{}'", command),
+            Error::UnknownOption(option) => write!(f, "unknown option '{}'", option),
+            Error::MissingValue(option) => write!(f, "option '{}' needs a value", option),
+            Error::InvalidYear(year) => {
+                write!(f, "invalid year '{}', expected a year from 2015 on", year)
+            }
+            Error::InvalidDay(day) => {
+                write!(f, "invalid day '{}', expected a day from 1 to 25", day)
+            }
+            Error::InvalidValue { option, value } => {
+                write!(f, "invalid value '{}' for option '{}'", value, option)
+            }
+            Error::UnexpectedArgument(argument) => {
+                write!(f, "unexpected argument '{}'", argument)
+            }
+            Error::Missing(what) => write!(f, "missing {}", what),
+        }
+    }
+}
+
+pub fn parse_year(year: &str) -> Result<u32, Error> {
+    year.parse::<u32>()
+        .ok()
+        .filter(|year| *year >= 2015)
+        .ok_or_else(|| Error::InvalidYear(year.to_string()))
+}
+
+pub fn parse_day(day: &str) -> Result<u32, Error> {
+    day.parse::<u32>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| Error::InvalidDay(day.to_string()))
+}
+
+/// Parses the arguments following the program name, reading defaults such as
+/// the inputs directory from `inputs`.
+pub fn parse(args: impl IntoIterator<Item = String>, inputs: Inputs) -> Result<Command, Error> {
+    let mut args = args.into_iter().peekable();
+    let command = match args.peek().map(String::as_str) {
+        None | Some("-h" | "--help" | "help") => return Ok(Command::Help),
+        Some(first) if first.starts_with(|c: char| c.is_ascii_digit()) => "run".to_string(),
+        Some(_) => args.next().expect("peeked command"),
+    };
+
+    let mut positional = Vec::new();
+    let mut selection = Selection::default();
+    let mut file = None;
+    let mut inputs = inputs;
+    let mut account = None;
+    let mut repeat = None;
+    let mut check = command == "check";
+    let mut record = false;
+    let mut format = Format::default();
+    let mut frames = None;
+    let mut scale = FRAME_SCALE;
+
+    while let Some(arg) = args.next() {
+        let mut value = |option: &str| args.next().ok_or(Error::MissingValue(option.into()));
+        let positive = |option: &str, count: String| {
+            count
+                .parse::<usize>()
+                .ok()
+                .filter(|n| *n > 0)
+                .ok_or(Error::InvalidValue {
+                    option: option.into(),
+                    value: count,
+                })
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--year" => selection.year = Some(parse_year(&value(&arg)?)?),
+            "--day" => selection.day = Some(parse_day(&value(&arg)?)?),
+            "--input" => file = Some(PathBuf::from(value(&arg)?)),
+            "--inputs" => inputs.root = PathBuf::from(value(&arg)?),
+            "--account" => account = Some(value(&arg)?),
+            "--repeat" => repeat = Some(positive(&arg, value(&arg)?)?),
+            "--check" => check = true,
+            "--record" => record = true,
+            "--format" => {
+                format = match value(&arg)?.as_str() {
+                    "table" => Format::Table,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    other => {
+                        return Err(Error::InvalidValue {
+                            option: arg,
+                            value: other.to_string(),
+                        })
+                    }
+                }
+            }
+            "--frames" => frames = Some(PathBuf::from(value(&arg)?)),
+            "--scale" => scale = positive(&arg, value(&arg)?)?,
+            "-" => positional.push(arg),
+            option if option.starts_with('-') => return Err(Error::UnknownOption(arg)),
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let mut next_year = |selection: &mut Selection| -> Result<(), Error> {
+        if let Some(year) = positional.next() {
+            selection.year = Some(parse_year(&year)?);
+        }
+        Ok(())
+    };
+    next_year(&mut selection)?;
+
+    let mut year_and_day = |selection: &mut Selection| -> Result<(u32, u32), Error> {
+        if let Some(day) = positional.next() {
+            selection.day = Some(parse_day(&day)?);
+        }
+        match *selection {
+            Selection {
+                year: Some(year),
+                day: Some(day),
+            } => Ok((year, day)),
+            Selection { year: None, .. } => Err(Error::Missing("year")),
+            Selection { day: None, .. } => Err(Error::Missing("day")),
+        }
+    };
+
+    let root = inputs.clone();
+    if let Some(name) = &account {
+        inputs = inputs.account(name);
+    }
+    match command.as_str() {
+        "list" => {
+            end(&mut positional)?;
+            Ok(Command::List(selection, inputs))
+        }
+        "new" => {
+            let (year, day) = year_and_day(&mut selection)?;
+            end(&mut positional)?;
+            Ok(Command::New { year, day })
+        }
+        "visualise" => {
+            let (year, day) = year_and_day(&mut selection)?;
+            end(&mut positional)?;
+            Ok(Command::Visualise(VisualiseOptions {
+                year,
+                day,
+                inputs,
+                file,
+                frames,
+                scale,
+            }))
+        }
+        "import" => {
+            let (year, day) = year_and_day(&mut selection)?;
+            let file = positional
+                .next()
+                .map(PathBuf::from)
+                .or(file)
+                .ok_or(Error::Missing("input file"))?;
+            end(&mut positional)?;
+            Ok(Command::Import {
+                year,
+                day,
+                file,
+                inputs,
+            })
+        }
+        "verify" => {
+            if let Some(day) = positional.next() {
+                selection.day = Some(parse_day(&day)?);
+            }
+            end(&mut positional)?;
+            Ok(Command::Verify {
+                selection,
+                inputs: root,
+                account,
+            })
+        }
+        "run" | "bench" | "check" => {
+            if let Some(day) = positional.next() {
+                selection.day = Some(parse_day(&day)?);
+            }
+            // The input file used to be the last positional argument.
+            if file.is_none() {
+                file = positional.next().map(PathBuf::from);
+            }
+            end(&mut positional)?;
+            let bench = command == "bench";
+            Ok(Command::Run(RunOptions {
+                selection,
+                inputs,
+                file,
+                repeat: repeat.unwrap_or(if bench { BENCH_REPEAT } else { 1 }),
+                spread: bench || repeat.is_some_and(|n| n > 1),
+                check,
+                record,
+                format,
+            }))
+        }
+        _ => Err(Error::UnknownCommand(command)),
+    }
+}
+
+/// Fails on the first positional argument that is left over.
+fn end(positional: &mut impl Iterator<Item = String>) -> Result<(), Error> {
+    match positional.next() {
+        Some(argument) => Err(Error::UnexpectedArgument(argument)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn parse_args(args: &str) -> Result<Command, Error> {
+        parse(args.split_whitespace().map(String::from), Inputs::default())
+    }
+
+    fn run_options(year: Option<u32>, day: Option<u32>) -> RunOptions {
+        RunOptions {
+            selection: Selection { year, day },
+            inputs: Inputs::default(),
+            file: None,
+            repeat: 1,
+            spread: false,
+            check: false,
+            record: false,
+            format: Format::Table,
+        }
+    }
+
+    #[rstest]
+    #[case("run", run_options(None, None))]
+    #[case("run 2024", run_options(Some(2024), None))]
+    #[case("run 2024 5", run_options(Some(2024), Some(5)))]
+    #[case("2024 5", run_options(Some(2024), Some(5)))]
+    #[case("run --day 5 --year 2015", run_options(Some(2015), Some(5)))]
+    #[case(
+        "run 2024 5 input.txt",
+        RunOptions { file: Some(PathBuf::from("input.txt")), ..run_options(Some(2024), Some(5)) }
+    )]
+    #[case(
+        "2024 5 -",
+        RunOptions { file: Some(PathBuf::from("-")), ..run_options(Some(2024), Some(5)) }
+    )]
+    fn parses_run(#[case] args: &str, #[case] options: RunOptions) {
+        assert_eq!(parse_args(args), Ok(Command::Run(options)));
+    }
+
+    #[test]
+    fn parses_run_options() {
+        assert_eq!(
+            parse_args(
+                "run 2024 6 --input - --inputs data --repeat 3 --check --record --format json"
+            ),
+            Ok(Command::Run(RunOptions {
+                inputs: Inputs::new("data"),
+                file: Some(PathBuf::from("-")),
+                repeat: 3,
+                spread: true,
+                check: true,
+                record: true,
+                format: Format::Json,
+                ..run_options(Some(2024), Some(6))
+            }))
+        );
+    }
+
+    #[test]
+    fn parses_bench_and_check() {
+        assert_eq!(
+            parse_args("bench 2024"),
+            Ok(Command::Run(RunOptions {
+                repeat: 10,
+                spread: true,
+                ..run_options(Some(2024), None)
+            }))
+        );
+        assert_eq!(
+            parse_args("check"),
+            Ok(Command::Run(RunOptions {
+                check: true,
+                ..run_options(None, None)
+            }))
+        );
+    }
+
+    #[rstest]
+    #[case("list", Command::List(Selection::default(), Inputs::default()))]
+    #[case(
+        "list 2015 --inputs data",
+        Command::List(Selection { year: Some(2015), day: None }, Inputs::new("data"))
+    )]
+    #[case("new 2015 4", Command::New { year: 2015, day: 4 })]
+    #[case(
+        "visualise 2024 6 --frames out --scale 2",
+        Command::Visualise(VisualiseOptions {
+            year: 2024,
+            day: 6,
+            inputs: Inputs::default(),
+            file: None,
+            frames: Some(PathBuf::from("out")),
+            scale: 2,
+        })
+    )]
+    #[case(
+        "import 2024 6 ~/input.txt --account work",
+        Command::Import {
+            year: 2024,
+            day: 6,
+            file: PathBuf::from("~/input.txt"),
+            inputs: Inputs::default().account("work"),
+        }
+    )]
+    #[case(
+        "verify 2024 --inputs data",
+        Command::Verify {
+            selection: Selection { year: Some(2024), day: None },
+            inputs: Inputs::new("data"),
+            account: None,
+        }
+    )]
+    #[case(
+        "run 2024 --account work",
+        Command::Run(RunOptions {
+            inputs: Inputs::default().account("work"),
+            ..run_options(Some(2024), None)
+        })
+    )]
+    #[case("", Command::Help)]
+    #[case("run 2024 --help", Command::Help)]
+    fn parses_other_commands(#[case] args: &str, #[case] command: Command) {
+        assert_eq!(parse_args(args), Ok(command));
+    }
+
+    #[rstest]
+    #[case("2O24 5", Error::InvalidYear("2O24".into()))]
+    #[case("run 2024 26", Error::InvalidDay("26".into()))]
+    #[case("run 1999", Error::InvalidYear("1999".into()))]
+    #[case("run --repeat", Error::MissingValue("--repeat".into()))]
+    #[case("run --repeat 0", Error::InvalidValue { option: "--repeat".into(), value: "0".into() })]
+    #[case("run --verbose", Error::UnknownOption("--verbose".into()))]
+    #[case("run --format xml", Error::InvalidValue { option: "--format".into(), value: "xml".into() })]
+    #[case("run 2024 5 a.txt b.txt", Error::UnexpectedArgument("b.txt".into()))]
+    #[case("run 2024 5 a.txt --input b.txt", Error::UnexpectedArgument("a.txt".into()))]
+    #[case("solve", Error::UnknownCommand("solve".into()))]
+    #[case("new 2024", Error::Missing("day"))]
+    #[case("new 2024 5 6", Error::UnexpectedArgument("6".into()))]
+    #[case("import 2024 5", Error::Missing("input file"))]
+    #[case("import 2024 5 a b", Error::UnexpectedArgument("b".into()))]
+    #[case("visualise --day 6", Error::Missing("year"))]
+    #[case("visualise 2024 6 --scale x", Error::InvalidValue { option: "--scale".into(), value: "x".into() })]
+    fn rejects_invalid_arguments(#[case] args: &str, #[case] error: Error) {
+        assert_eq!(parse_args(args), Err(error));
+    }
+}
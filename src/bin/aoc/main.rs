@@ -0,0 +1,579 @@
+mod cli;
+mod report;
+mod scaffold;
+
+use aoc::answers::{KnownAnswers, Status, ANSWERS};
+use aoc::inputs::Inputs;
+use aoc::parallel;
+use aoc::render;
+use aoc::runner::{self, format_duration, run, run_with_input, Answers, Summary};
+use aoc::store::{self, Checksums, CHECKSUMS};
+use aoc::{solutions, Solution};
+use cli::{Command, Format, RunOptions, Selection, VisualiseOptions, USAGE};
+use itertools::Itertools;
+use report::Report;
+use std::fs::read_to_string;
+use std::io;
+use std::io::stdin;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    match cli::parse(std::env::args().skip(1), Inputs::from_env()) {
+        Ok(Command::Help) => {
+            print!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Run(options)) => run_solutions(options),
+        Ok(Command::List(selection, inputs)) => list(selection, &inputs),
+        Ok(Command::New { year, day }) => new_day(year, day),
+        Ok(Command::Visualise(options)) => visualise(options),
+        Ok(Command::Import {
+            year,
+            day,
+            file,
+            inputs,
+        }) => import(year, day, file, &inputs),
+        Ok(Command::Verify {
+            selection,
+            inputs,
+            account,
+        }) => verify(selection, &inputs, account),
+        Err(error) => {
+            eprintln!(
+                "error: {}\n\nFor more information, try 'aoc --help'.",
+                error
+            );
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn select(Selection { year, day }: Selection) -> Vec<Solution> {
+    solutions()
+        .into_iter()
+        .filter(|solution| year.is_none_or(|y: u32| y == solution.year))
+        .filter(|solution| day.is_none_or(|d: u32| d == solution.day))
+        .collect()
+}
+
+fn run_solutions(
+    RunOptions {
+        selection,
+        inputs,
+        file,
+        repeat,
+        spread,
+        check,
+        record,
+        format,
+    }: RunOptions,
+) -> ExitCode {
+    let selected = select(selection);
+
+    if selected.is_empty() {
+        match (selection.day, selection.year) {
+            (None, None) => {
+                eprintln!("No solution found!");
+            }
+            (None, Some(year)) => {
+                eprintln!("No solution found for year {}", year)
+            }
+            (Some(day), None) => {
+                eprintln!("No solution found for day {}", day)
+            }
+            (Some(day), Some(year)) => {
+                eprintln!("No solution found for year {}, day {:02}!", year, day);
+            }
+        }
+        return ExitCode::FAILURE;
+    }
+    let answers_path = inputs.file(ANSWERS);
+    let mut known = match KnownAnswers::load(&answers_path) {
+        Ok(known) => known,
+        Err(error) => {
+            eprintln!("Cannot read {}: {}", answers_path.display(), error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let explicit_input = match file {
+        Some(file) => match read_input(&file) {
+            Ok(data) => Some(data),
+            Err(error) => {
+                eprintln!("Cannot read {}: {}", input_name(&file), error);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => None,
+    };
+    let run_solution = |solution: &Solution| match &explicit_input {
+        Some(data) => run(solution, data, repeat).map_err(runner::Error::Parse),
+        None => run_with_input(solution, &inputs, repeat),
+    };
+    // Days running at the same time compete for the cores, so benchmarks run one at a time.
+    let concurrent = cfg!(feature = "parallel") && !spread && selected.len() > 1;
+    let results = if spread {
+        selected.iter().map(run_solution).collect::<Vec<_>>()
+    } else {
+        parallel::map(&selected, run_solution)
+    };
+
+    let statuses = selected
+        .iter()
+        .zip(&results)
+        .map(|(solution, answers)| match (check, answers) {
+            (true, Ok(answers)) => parts(solution, answers)
+                .map(|(part, answer)| known.check(solution.year, solution.day, part, answer))
+                .collect(),
+            _ => Vec::new(),
+        })
+        .collect::<Vec<Vec<Status>>>();
+    let failed = statuses
+        .iter()
+        .flatten()
+        .any(|status| matches!(status, Status::Fail { .. }));
+
+    match format {
+        Format::Table => {
+            let mut header = vec!["Year", "Day", "Part 1", "Part 2"];
+            if concurrent {
+                header.extend(["Parse*", "Time 1*", "Time 2*", "Total*"]);
+            } else {
+                header.extend(["Parse", "Time 1", "Time 2", "Total"]);
+            }
+            if check {
+                header.push("Check");
+            }
+            let rows = selected
+                .iter()
+                .zip(&results)
+                .zip(&statuses)
+                .map(|((solution, answers), statuses)| {
+                    let mut row = row(solution, answers, spread);
+                    if check {
+                        row.push(statuses.iter().join(" / "));
+                    }
+                    row
+                })
+                .collect::<Vec<_>>();
+            print_table(&header, &rows);
+            if concurrent {
+                println!(
+                    "\n* measured while the days ran concurrently, use --repeat or bench to time \
+                     them one at a time"
+                );
+            }
+        }
+        Format::Json | Format::Csv => {
+            let reports = selected
+                .iter()
+                .zip(&results)
+                .zip(&statuses)
+                .map(|((solution, answers), statuses)| Report {
+                    year: solution.year,
+                    day: solution.day,
+                    parts: solution.parts,
+                    answers,
+                    statuses,
+                    concurrent,
+                })
+                .collect::<Vec<_>>();
+            if format == Format::Json {
+                print!("{}", report::json(&reports));
+            } else {
+                print!("{}", report::csv(&reports));
+            }
+        }
+    }
+
+    if record {
+        let mut recorded = 0;
+        for (solution, answers) in selected.iter().zip(&results) {
+            let Ok(answers) = answers else { continue };
+            for (part, answer) in parts(solution, answers) {
+                match known.record(solution.year, solution.day, part, answer) {
+                    Ok(added) => recorded += usize::from(added),
+                    Err(error) => eprintln!(
+                        "warning: not recording year {}, day {:02}, part {}: {}",
+                        solution.year, solution.day, part, error
+                    ),
+                }
+            }
+        }
+        if let Err(error) = known.save(&answers_path) {
+            eprintln!("Cannot write {}: {}", answers_path.display(), error);
+            return ExitCode::FAILURE;
+        }
+        let message = format!(
+            "Recorded {} new answers in {}",
+            recorded,
+            answers_path.display()
+        );
+        // Keeps machine-readable output on stdout parseable.
+        if format == Format::Table {
+            println!("{}", message);
+        } else {
+            eprintln!("{}", message);
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Reads an explicit input file, or stdin for `-`.
+fn read_input(file: &Path) -> io::Result<String> {
+    if is_stdin(file) {
+        let mut buffer = String::new();
+        eprintln!("Reading input until EOF:");
+        stdin().lock().read_to_string(&mut buffer)?;
+        Ok(buffer)
+    } else {
+        read_to_string(file)
+    }
+}
+
+fn is_stdin(file: &Path) -> bool {
+    file.as_os_str() == "-"
+}
+
+/// The input file as named in messages.
+fn input_name(file: &Path) -> String {
+    if is_stdin(file) {
+        "stdin".to_string()
+    } else {
+        file.display().to_string()
+    }
+}
+
+/// The answers of the parts the puzzle has.
+fn parts<'a>(solution: &Solution, answers: &'a Answers) -> impl Iterator<Item = (u8, &'a String)> {
+    [(1, &answers.part1), (2, &answers.part2)]
+        .into_iter()
+        .take(solution.parts as usize)
+}
+
+fn list(selection: Selection, inputs: &Inputs) -> ExitCode {
+    let selected = select(selection);
+    if selected.is_empty() {
+        eprintln!("No solution found!");
+        return ExitCode::FAILURE;
+    }
+    let answers_path = inputs.file(ANSWERS);
+    let known = match KnownAnswers::load(&answers_path) {
+        Ok(known) => known,
+        Err(error) => {
+            eprintln!("Cannot read {}: {}", answers_path.display(), error);
+            return ExitCode::FAILURE;
+        }
+    };
+    for (year, days) in &selected.iter().chunk_by(|solution| solution.year) {
+        println!("{}:", year);
+        let rows = days
+            .map(|solution| {
+                let answered = (1..=solution.parts)
+                    .filter(|part| known.get(year, solution.day, *part).is_some())
+                    .count();
+                vec![
+                    format!("{:02}", solution.day),
+                    (1..=solution.parts).join(" + "),
+                    if inputs.path(year, solution.day).exists() {
+                        "present".to_string()
+                    } else {
+                        "missing".to_string()
+                    },
+                    format!("{}/{}", answered, solution.parts),
+                ]
+            })
+            .collect::<Vec<_>>();
+        print_table(&["Day", "Parts", "Input", "Answers"], &rows);
+        println!();
+    }
+    ExitCode::SUCCESS
+}
+
+fn new_day(year: u32, day: u32) -> ExitCode {
+    let source = match std::env::current_dir() {
+        Ok(current) => match scaffold::source_directory(&current) {
+            Some(source) => source,
+            None => {
+                eprintln!(
+                    "Cannot find src/template.rs in {} or above, run 'aoc new' inside the checkout",
+                    current.display()
+                );
+                return ExitCode::FAILURE;
+            }
+        },
+        Err(error) => {
+            eprintln!("Cannot read the current directory: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let module = source
+        .join(format!("year_{}", year))
+        .join(format!("day{:02}.rs", day));
+    if module.exists() {
+        eprintln!("{} already exists", module.display());
+        return ExitCode::FAILURE;
+    }
+    let created = read_to_string(source.join("template.rs")).and_then(|template| {
+        if let Some(directory) = module.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+        std::fs::write(&module, scaffold::instantiate(&template, year, day))
+    });
+    if let Err(error) = created {
+        eprintln!("Cannot create year {}, day {:02}: {}", year, day, error);
+        return ExitCode::FAILURE;
+    }
+    println!("Created {}", module.display());
+    ExitCode::SUCCESS
+}
+
+fn visualise(
+    VisualiseOptions {
+        year,
+        day,
+        inputs,
+        file,
+        frames,
+        scale,
+    }: VisualiseOptions,
+) -> ExitCode {
+    let selection = Selection {
+        year: Some(year),
+        day: Some(day),
+    };
+    let Some(solution) = select(selection).pop() else {
+        eprintln!("No solution found for year {}, day {:02}!", year, day);
+        return ExitCode::FAILURE;
+    };
+    let data = match file {
+        Some(file) => match read_input(&file) {
+            Ok(data) => data,
+            Err(error) => {
+                eprintln!("Cannot read {}: {}", input_name(&file), error);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => match inputs.read(year, day) {
+            Ok(data) => data,
+            Err(error) => {
+                let path = inputs.path(year, day);
+                eprintln!("Cannot read {}: {}", path.display(), error);
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+    let pictures = match solution.parse(&data) {
+        Ok(input) => solution.visualise(input.as_ref()),
+        Err(error) => {
+            eprintln!("Invalid input: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(last) = pictures.last() else {
+        eprintln!("Year {}, day {:02} cannot be visualised", year, day);
+        return ExitCode::FAILURE;
+    };
+    print!("{}", last.ansi());
+    if let Some(directory) = frames {
+        if let Err(error) = render::write_frames(&directory, &pictures, scale) {
+            eprintln!("Cannot write frames to {}: {}", directory.display(), error);
+            return ExitCode::FAILURE;
+        }
+        println!("Wrote {} frames to {}", pictures.len(), directory.display());
+    }
+    ExitCode::SUCCESS
+}
+
+fn import(year: u32, day: u32, file: PathBuf, inputs: &Inputs) -> ExitCode {
+    let data = match read_input(&file) {
+        Ok(data) => data,
+        Err(error) => {
+            eprintln!("Cannot read {}: {}", input_name(&file), error);
+            return ExitCode::FAILURE;
+        }
+    };
+    match store::import(inputs, year, day, &data) {
+        Ok(problems) => {
+            for problem in problems {
+                if problem.is_normalised() {
+                    eprintln!("warning: the input had {}, stored it normalised", problem);
+                } else {
+                    eprintln!(
+                        "warning: {}, the input is possibly truncated, stored it as is",
+                        problem
+                    );
+                }
+            }
+            println!("Stored {}", inputs.path(year, day).display());
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("Cannot import year {}, day {:02}: {}", year, day, error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn verify(selection: Selection, root: &Inputs, account: Option<String>) -> ExitCode {
+    let accounts = match account {
+        Some(name) => vec![(name.clone(), root.account(&name))],
+        None => match root.accounts() {
+            Ok(names) => [("default".to_string(), root.clone())]
+                .into_iter()
+                .chain(names.into_iter().map(|name| {
+                    let inputs = root.account(&name);
+                    (name, inputs)
+                }))
+                .collect(),
+            Err(error) => {
+                eprintln!(
+                    "Cannot list the accounts in {}: {}",
+                    root.root.display(),
+                    error
+                );
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+
+    let mut rows = Vec::new();
+    let mut failed = false;
+    for (name, inputs) in accounts {
+        let checksums_path = inputs.file(CHECKSUMS);
+        let checksums = match Checksums::load(&checksums_path) {
+            Ok(checksums) => checksums,
+            Err(error) => {
+                eprintln!("Cannot read {}: {}", checksums_path.display(), error);
+                return ExitCode::FAILURE;
+            }
+        };
+        // Imported inputs and the ones placed there by hand for an implemented day.
+        let days = checksums
+            .days()
+            .chain(
+                select(selection)
+                    .iter()
+                    .map(|solution| (solution.year, solution.day))
+                    .filter(|(year, day)| inputs.path(*year, *day).exists()),
+            )
+            .filter(|(year, day)| {
+                selection.year.is_none_or(|y| y == *year) && selection.day.is_none_or(|d| d == *day)
+            })
+            .sorted()
+            .dedup();
+        for (year, day) in days {
+            let mut row = vec![name.clone(), year.to_string(), format!("{:02}", day)];
+            match inputs.read(year, day) {
+                Ok(data) => {
+                    let problems = store::verify(&data, checksums.get(year, day));
+                    failed |= !problems.is_empty();
+                    row.extend([
+                        data.len().to_string(),
+                        store::Checksum::of(&data).sha256[..16].to_string(),
+                        if problems.is_empty() {
+                            "ok".to_string()
+                        } else {
+                            problems.iter().join(", ")
+                        },
+                    ]);
+                }
+                Err(error) => {
+                    failed = true;
+                    row.extend([
+                        String::new(),
+                        String::new(),
+                        runner::Error::Input(error).to_string(),
+                    ]);
+                }
+            }
+            rows.push(row);
+        }
+    }
+    print_table(
+        &["Account", "Year", "Day", "Bytes", "SHA-256", "Status"],
+        &rows,
+    );
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn row(solution: &Solution, answers: &Result<Answers, runner::Error>, spread: bool) -> Vec<String> {
+    let mut row = vec![solution.year.to_string(), format!("{:02}", solution.day)];
+    match answers {
+        Ok(Answers {
+            part1,
+            part2,
+            timings,
+        }) => {
+            let phase = |summary: Summary| {
+                if spread {
+                    format!(
+                        "{} / {} / {}",
+                        format_duration(summary.min),
+                        format_duration(summary.median),
+                        format_duration(summary.max)
+                    )
+                } else {
+                    format_duration(summary.median)
+                }
+            };
+            row.extend([
+                part1.clone(),
+                part2.clone(),
+                phase(timings.parse),
+                phase(timings.part1),
+                phase(timings.part2),
+                format_duration(timings.total()),
+            ])
+        }
+        Err(error) => row.push(error.to_string()),
+    }
+    row.resize(8, String::new());
+    row
+}
+
+fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let widths = header
+        .iter()
+        .enumerate()
+        .map(|(column, title)| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([title.chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let print_row = |cells: Vec<&str>| {
+        let line = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        println!("{}", line.trim_end());
+    };
+    print_row(header.to_vec());
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-+-")
+    );
+    for row in rows {
+        print_row(row.iter().map(String::as_str).collect());
+    }
+}
@@ -0,0 +1,262 @@
+//! Machine-readable results of a run, as JSON or CSV with timings in nanoseconds.
+
+use aoc::answers::Status;
+use aoc::runner::{self, Answers, Summary};
+use std::fmt::Write;
+
+/// The outcome of one solution.
+pub struct Report<'a> {
+    pub year: u32,
+    pub day: u32,
+    pub parts: u8,
+    pub answers: &'a Result<Answers, runner::Error>,
+    /// One status per part when the answers were checked, empty otherwise.
+    pub statuses: &'a [Status],
+    /// Whether the timings were measured while other days ran at the same time.
+    pub concurrent: bool,
+}
+
+impl Report<'_> {
+    fn answer(&self, part: u8) -> Option<&str> {
+        let answers = self.answers.as_ref().ok()?;
+        match part {
+            1 => Some(&answers.part1),
+            _ if self.parts > 1 => Some(&answers.part2),
+            _ => None,
+        }
+    }
+
+    fn error(&self) -> Option<String> {
+        self.answers.as_ref().err().map(ToString::to_string)
+    }
+}
+
+/// An array with one object per solution.
+pub fn json(reports: &[Report]) -> String {
+    let objects = reports
+        .iter()
+        .map(|report| {
+            let mut object = format!("{{\"year\": {}, \"day\": {}", report.year, report.day);
+            for part in 1..=2 {
+                let answer = report.answer(part).map_or("null".to_string(), json_string);
+                write!(object, ", \"part{}\": {}", part, answer).expect("writing to a string");
+            }
+            if let Ok(Answers { timings, .. }) = report.answers {
+                let summary = |summary: Summary| {
+                    format!(
+                        "{{\"min\": {}, \"median\": {}, \"max\": {}}}",
+                        summary.min.as_nanos(),
+                        summary.median.as_nanos(),
+                        summary.max.as_nanos()
+                    )
+                };
+                write!(
+                    object,
+                    ", \"timings\": {{\"parse\": {}, \"part1\": {}, \"part2\": {}, \"total\": {}, \
+                     \"concurrent\": {}}}",
+                    summary(timings.parse),
+                    summary(timings.part1),
+                    summary(timings.part2),
+                    timings.total().as_nanos(),
+                    report.concurrent
+                )
+                .expect("writing to a string");
+            }
+            if !report.statuses.is_empty() {
+                let statuses = report
+                    .statuses
+                    .iter()
+                    .map(|status| match status {
+                        Status::Fail { expected } => format!(
+                            "{{\"status\": \"fail\", \"expected\": {}}}",
+                            json_string(expected)
+                        ),
+                        status => format!("{{\"status\": \"{}\"}}", name(status)),
+                    })
+                    .collect::<Vec<_>>();
+                write!(object, ", \"check\": [{}]", statuses.join(", "))
+                    .expect("writing to a string");
+            }
+            if let Some(error) = report.error() {
+                write!(object, ", \"error\": {}", json_string(&error))
+                    .expect("writing to a string");
+            }
+            object + "}"
+        })
+        .collect::<Vec<_>>();
+    if objects.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n  {}\n]\n", objects.join(",\n  "))
+    }
+}
+
+/// A header row and one row per solution with the median timings.
+pub fn csv(reports: &[Report]) -> String {
+    let mut text =
+        "year,day,part1,part2,parse_ns,part1_ns,part2_ns,total_ns,concurrent,check1,check2,error\n"
+            .to_string();
+    for report in reports {
+        let mut fields = vec![report.year.to_string(), report.day.to_string()];
+        fields.extend((1..=2).map(|part| report.answer(part).map_or(String::new(), csv_field)));
+        match report.answers {
+            Ok(Answers { timings, .. }) => fields.extend(
+                [
+                    timings.parse.median,
+                    timings.part1.median,
+                    timings.part2.median,
+                    timings.total(),
+                ]
+                .map(|duration| duration.as_nanos().to_string())
+                .into_iter()
+                .chain([report.concurrent.to_string()]),
+            ),
+            Err(_) => fields.extend(vec![String::new(); 5]),
+        }
+        fields.extend((0..2).map(|part| {
+            report
+                .statuses
+                .get(part)
+                .map_or(String::new(), |status| name(status).to_string())
+        }));
+        fields.push(report.error().as_deref().map_or(String::new(), csv_field));
+        text.push_str(&fields.join(","));
+        text.push('\n');
+    }
+    text
+}
+
+fn name(status: &Status) -> &'static str {
+    match status {
+        Status::Pass => "pass",
+        Status::Fail { .. } => "fail",
+        Status::Missing => "missing",
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for char in text.chars() {
+        match char {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            char if char.is_control() => {
+                write!(quoted, "\\u{:04x}", char as u32).expect("writing to a string")
+            }
+            char => quoted.push(char),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Quotes fields that contain separators, quotes or line breaks.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::runner::Timings;
+    use aoc::ParseError;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    fn summary(nanos: u64) -> Summary {
+        Summary {
+            min: Duration::from_nanos(nanos - 1),
+            median: Duration::from_nanos(nanos),
+            max: Duration::from_nanos(nanos + 1),
+        }
+    }
+
+    fn results() -> [Result<Answers, runner::Error>; 2] {
+        [
+            Ok(Answers {
+                part1: "11".to_string(),
+                part2: "a,\"b\"\n".to_string(),
+                timings: Timings {
+                    parse: summary(10),
+                    part1: summary(20),
+                    part2: summary(30),
+                },
+            }),
+            Err(runner::Error::Parse(ParseError::new(1, 2, "a digit"))),
+        ]
+    }
+
+    fn reports<'a>(
+        results: &'a [Result<Answers, runner::Error>; 2],
+        statuses: &'a [Status],
+    ) -> [Report<'a>; 2] {
+        [
+            Report {
+                year: 2024,
+                day: 1,
+                parts: 2,
+                answers: &results[0],
+                statuses,
+                concurrent: false,
+            },
+            Report {
+                year: 2015,
+                day: 25,
+                parts: 1,
+                answers: &results[1],
+                statuses: &[],
+                concurrent: true,
+            },
+        ]
+    }
+
+    #[test]
+    fn writes_json() {
+        let results = results();
+        let statuses = [
+            Status::Pass,
+            Status::Fail {
+                expected: "12".to_string(),
+            },
+        ];
+        assert_eq!(
+            json(&reports(&results, &statuses)),
+            "[\n  {\"year\": 2024, \"day\": 1, \"part1\": \"11\", \"part2\": \"a,\\\"b\\\"\\n\", \
+             \"timings\": {\"parse\": {\"min\": 9, \"median\": 10, \"max\": 11}, \
+             \"part1\": {\"min\": 19, \"median\": 20, \"max\": 21}, \
+             \"part2\": {\"min\": 29, \"median\": 30, \"max\": 31}, \"total\": 60, \
+             \"concurrent\": false}, \
+             \"check\": [{\"status\": \"pass\"}, {\"status\": \"fail\", \"expected\": \"12\"}]},\n  \
+             {\"year\": 2015, \"day\": 25, \"part1\": null, \"part2\": null, \
+             \"error\": \"invalid input: line 1, column 2: a digit\"}\n]\n"
+        );
+        assert_eq!(json(&[]), "[]\n");
+    }
+
+    #[test]
+    fn writes_csv() {
+        let results = results();
+        assert_eq!(
+            csv(&reports(&results, &[Status::Missing, Status::Pass])),
+            "year,day,part1,part2,parse_ns,part1_ns,part2_ns,total_ns,concurrent,check1,check2,error\n\
+             2024,1,11,\"a,\"\"b\"\"\n\",10,20,30,60,false,missing,pass,\n\
+             2015,25,,,,,,,,,,\"invalid input: line 1, column 2: a digit\"\n"
+        );
+    }
+
+    #[rstest]
+    #[case("plain", "\"plain\"")]
+    #[case("say \"hi\"\\", "\"say \\\"hi\\\"\\\\\"")]
+    #[case("tab\there\u{1}", "\"tab\\there\\u0001\"")]
+    fn escapes_json_strings(#[case] text: &str, #[case] quoted: &str) {
+        assert_eq!(json_string(text), quoted);
+    }
+}
@@ -0,0 +1,56 @@
+//! Creating a new day from `src/template.rs`, shared with `build.rs`, which compiles the template
+//! filled in the same way.
+
+use std::path::{Path, PathBuf};
+
+/// The `src` directory of the checkout that `start` is in, found at the nearest `Cargo.toml`
+/// next to a `src/template.rs`.
+pub fn source_directory(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|directory| {
+            directory.join("Cargo.toml").is_file()
+                && directory.join("src").join("template.rs").is_file()
+        })
+        .map(|directory| directory.join("src"))
+}
+
+/// Fills in the year and day of the solution template.
+pub fn instantiate(template: &str, year: u32, day: u32) -> String {
+    template.replacen(
+        "crate::solver!(YEAR, DAY,",
+        &format!("crate::solver!({}, {},", year, day),
+        1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instantiates_template() {
+        assert_eq!(
+            instantiate(
+                "use crate::ParseError;\n\ncrate::solver!(YEAR, DAY, Input);\n",
+                2015,
+                4
+            ),
+            "use crate::ParseError;\n\ncrate::solver!(2015, 4, Input);\n"
+        );
+    }
+
+    #[test]
+    fn finds_source_directory() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert_eq!(
+            source_directory(&root.join("src").join("year_2015")),
+            Some(root.join("src"))
+        );
+        assert_eq!(
+            source_directory(&root.join("target")),
+            Some(root.join("src"))
+        );
+        assert_eq!(source_directory(Path::new("/")), None);
+    }
+}
@@ -0,0 +1,198 @@
+//! Cycle detection for sequences of states produced by repeatedly applying a step function.
+//!
+//! Step functions return `None` when the sequence ends, in which case there is no cycle.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The states from index `start` on repeat every `length` steps.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Index of the first state equal to the state at index `n`.
+    pub fn equivalent(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Floyd's tortoise and hare, which keeps only two states in memory.
+pub fn floyd<S>(initial: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Cycle>
+where
+    S: Clone + PartialEq,
+{
+    let mut tortoise = step(&initial)?;
+    let mut hare = step(&tortoise)?;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        let next = step(&hare)?;
+        hare = step(&next)?;
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise)?;
+    while tortoise != hare {
+        hare = step(&hare)?;
+        length += 1;
+    }
+    Some(Cycle { start, length })
+}
+
+/// Brent's algorithm, which keeps two states like [`floyd`] but needs fewer steps.
+pub fn brent<S>(initial: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Cycle>
+where
+    S: Clone + PartialEq,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial)?;
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare)?;
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+    Some(Cycle { start, length })
+}
+
+/// Remembers every state, which finds the cycle in a single pass at the cost of memory.
+pub fn hashed<S>(initial: S, step: impl FnMut(&S) -> Option<S>) -> Option<Cycle>
+where
+    S: Clone + Eq + Hash,
+{
+    let (cycle, _) = walk(initial, step, usize::MAX);
+    cycle
+}
+
+/// The state after `n` steps, skipping ahead once the sequence repeats.
+///
+/// Returns `None` if the sequence ends earlier.
+pub fn state_after<S>(initial: S, step: impl FnMut(&S) -> Option<S>, n: usize) -> Option<S>
+where
+    S: Clone + Eq + Hash,
+{
+    let (cycle, mut states) = walk(initial, step, n);
+    let index = cycle.map_or(n, |cycle| cycle.equivalent(n));
+    (index < states.len()).then(|| states.swap_remove(index))
+}
+
+/// Steps through the sequence until a state repeats, it ends, or `limit` steps have been taken.
+fn walk<S>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+    limit: usize,
+) -> (Option<Cycle>, Vec<S>)
+where
+    S: Clone + Eq + Hash,
+{
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut states = vec![initial];
+    while states.len() <= limit {
+        let Some(next) = step(states.last().expect("initial state")) else {
+            break;
+        };
+        if let Some(start) = seen.get(&next) {
+            let cycle = Cycle {
+                start: *start,
+                length: states.len() - start,
+            };
+            return (Some(cycle), states);
+        }
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+    (None, states)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    /// 0 → 1 → 2 → 3 → 4 → 5 → 2 → …
+    fn rho(state: &usize) -> Option<usize> {
+        Some([1, 2, 3, 4, 5, 2][*state])
+    }
+
+    /// 0 → 1 → 2, then ends.
+    fn line(state: &usize) -> Option<usize> {
+        [Some(1), Some(2), None][*state]
+    }
+
+    type Detector = fn(usize, fn(&usize) -> Option<usize>) -> Option<Cycle>;
+
+    #[rstest]
+    #[case::floyd(floyd)]
+    #[case::brent(brent)]
+    #[case::hashed(hashed)]
+    fn finds_cycle(#[case] detect: Detector) {
+        assert_eq!(
+            detect(0, rho),
+            Some(Cycle {
+                start: 2,
+                length: 4
+            })
+        );
+        assert_eq!(
+            detect(3, rho),
+            Some(Cycle {
+                start: 0,
+                length: 4
+            })
+        );
+        assert_eq!(
+            detect(0, |_| Some(0)),
+            Some(Cycle {
+                start: 0,
+                length: 1
+            })
+        );
+        assert_eq!(detect(0, line), None);
+    }
+
+    #[rstest]
+    #[case(0, Some(0))]
+    #[case(5, Some(5))]
+    #[case(10, Some(2))]
+    #[case(1_000_000_000, Some(4))]
+    fn extrapolates_state(#[case] n: usize, #[case] state: Option<usize>) {
+        assert_eq!(state_after(0, rho, n), state);
+    }
+
+    #[test]
+    fn stops_at_end_of_sequence() {
+        assert_eq!(state_after(0, line, 2), Some(2));
+        assert_eq!(state_after(0, line, 3), None);
+    }
+}
@@ -0,0 +1,218 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Position or offset on a 2D grid, with rows growing downwards.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub row: isize,
+    pub column: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    /// Offsets to the four orthogonal neighbours, clockwise from up.
+    pub const ORTHOGONAL: [Point; 4] = [
+        Point::new(-1, 0),
+        Point::new(0, 1),
+        Point::new(1, 0),
+        Point::new(0, -1),
+    ];
+
+    /// Offsets to the four diagonal neighbours, clockwise from up-left.
+    pub const DIAGONAL: [Point; 4] = [
+        Point::new(-1, -1),
+        Point::new(-1, 1),
+        Point::new(1, 1),
+        Point::new(1, -1),
+    ];
+
+    /// Offsets to all eight neighbours, clockwise from up.
+    pub const ADJACENT: [Point; 8] = [
+        Point::new(-1, 0),
+        Point::new(-1, 1),
+        Point::new(0, 1),
+        Point::new(1, 1),
+        Point::new(1, 0),
+        Point::new(1, -1),
+        Point::new(0, -1),
+        Point::new(-1, -1),
+    ];
+
+    pub const fn new(row: isize, column: isize) -> Point {
+        Point { row, column }
+    }
+
+    /// The four orthogonal neighbours.
+    pub fn neighbours(self) -> impl Iterator<Item = Point> {
+        Point::ORTHOGONAL
+            .into_iter()
+            .map(move |offset| self + offset)
+    }
+
+    /// The eight orthogonal and diagonal neighbours.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Point::ADJACENT.into_iter().map(move |offset| self + offset)
+    }
+
+    pub fn manhattan(self, other: Point) -> usize {
+        self.row.abs_diff(other.row) + self.column.abs_diff(other.column)
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((row, column): (isize, isize)) -> Point {
+        Point::new(row, column)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.row + other.row, self.column + other.column)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, direction: Direction) -> Point {
+        self + direction.offset()
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.row - other.row, self.column - other.column)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: isize) -> Point {
+        Point::new(self.row * factor, self.column * factor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.row, -self.column)
+    }
+}
+
+/// One of the four orthogonal directions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+use Direction::*;
+
+impl Direction {
+    /// All directions, clockwise from up.
+    pub const ALL: [Direction; 4] = [Up, Right, Down, Left];
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Up => Right,
+            Right => Down,
+            Down => Left,
+            Left => Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Up => Left,
+            Right => Up,
+            Down => Right,
+            Left => Down,
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Up => Down,
+            Right => Left,
+            Down => Up,
+            Left => Right,
+        }
+    }
+
+    /// Offset of a single step in this direction.
+    pub fn offset(self) -> Point {
+        Point::ORTHOGONAL[self as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn does_arithmetic() {
+        let point = Point::new(2, -3);
+        assert_eq!(point + Point::new(1, 1), Point::new(3, -2));
+        assert_eq!(point - Point::new(1, 1), Point::new(1, -4));
+        assert_eq!(point * 3, Point::new(6, -9));
+        assert_eq!(-point, Point::new(-2, 3));
+        assert_eq!(point + Left, Point::new(2, -4));
+        assert_eq!(point.manhattan(Point::new(-1, 1)), 7);
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        assert_eq!(
+            Point::new(1, 1).neighbours().collect::<Vec<_>>(),
+            vec![
+                Point::new(0, 1),
+                Point::new(1, 2),
+                Point::new(2, 1),
+                Point::new(1, 0)
+            ]
+        );
+        let mut adjacent = Point::ORIGIN.neighbours8().collect::<Vec<_>>();
+        adjacent.sort();
+        let mut expected = [Point::ORTHOGONAL, Point::DIAGONAL].concat();
+        expected.sort();
+        assert_eq!(adjacent, expected);
+    }
+
+    #[rstest]
+    #[case(Up, Right, Left, Down)]
+    #[case(Right, Down, Up, Left)]
+    #[case(Down, Left, Right, Up)]
+    #[case(Left, Up, Down, Right)]
+    fn rotates(
+        #[case] direction: Direction,
+        #[case] right: Direction,
+        #[case] left: Direction,
+        #[case] opposite: Direction,
+    ) {
+        assert_eq!(direction.turn_right(), right);
+        assert_eq!(direction.turn_left(), left);
+        assert_eq!(direction.opposite(), opposite);
+        assert_eq!(direction.offset(), -opposite.offset());
+    }
+}
@@ -0,0 +1,230 @@
+use crate::geometry::Point;
+use crate::ParseError;
+use std::ops::{Index, IndexMut};
+
+/// Dense rectangular grid stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cell: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![cell; width * height],
+        }
+    }
+
+    /// Parses a grid with one character per cell, mapping each character with `cell`.
+    ///
+    /// Characters without a mapping are reported as errors, using `expected` to describe the
+    /// valid ones.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+        for (row, line) in input.lines().enumerate() {
+            let mut columns = 0;
+            for (column, char) in line.chars().enumerate() {
+                cells.push(cell(char).ok_or_else(|| {
+                    ParseError::new(
+                        row + 1,
+                        column + 1,
+                        format!("expected {}, found {:?}", expected, char),
+                    )
+                })?);
+                columns += 1;
+            }
+            match width {
+                None => width = Some(columns),
+                Some(width) if width != columns => {
+                    return Err(ParseError::new(
+                        row + 1,
+                        columns.min(width) + 1,
+                        format!("expected {} cells in the row, found {}", width, columns),
+                    ))
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.height as isize).contains(&point.row)
+            && (0..self.width as isize).contains(&point.column)
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.row as usize * self.width + point.column as usize)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(|offset| &mut self.cells[offset])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        self.cells
+            .iter()
+            .skip(column)
+            .step_by(self.width.max(1))
+            .take(if column < self.width { self.height } else { 0 })
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|column| self.column(column))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as isize;
+        (0..self.height as isize)
+            .flat_map(move |row| (0..width).map(move |column| Point::new(row, column)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Position of the first cell, row by row, that satisfies `predicate`.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point).expect("position inside the grid")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point).expect("position inside the grid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+123
+456
+";
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(input, "a digit", |char| char.to_digit(10))
+    }
+
+    #[test]
+    fn parses_grid() {
+        let grid = digits(EXAMPLE).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point::new(1, 0)), Some(&4));
+        assert_eq!(grid[Point::new(0, 2)], 3);
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+    }
+
+    #[test]
+    fn rejects_invalid_grids() {
+        assert_eq!(
+            digits("123\n4x6"),
+            Err(ParseError::new(2, 2, "expected a digit, found 'x'"))
+        );
+        assert_eq!(
+            digits("123\n45\n"),
+            Err(ParseError::new(
+                2,
+                3,
+                "expected 3 cells in the row, found 2"
+            ))
+        );
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = digits(EXAMPLE).unwrap();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]]
+        );
+        assert_eq!(grid.column(3).count(), 0);
+    }
+
+    #[test]
+    fn iterates_positions() {
+        let grid = digits(EXAMPLE).unwrap();
+        assert_eq!(
+            grid.iter()
+                .filter(|(_, digit)| **digit % 2 == 0)
+                .collect::<Vec<_>>(),
+            vec![
+                (Point::new(0, 1), &2),
+                (Point::new(1, 0), &4),
+                (Point::new(1, 2), &6)
+            ]
+        );
+        assert_eq!(grid.find(|digit| *digit > 4), Some(Point::new(1, 1)));
+    }
+
+    #[test]
+    fn maps_and_updates_cells() {
+        let mut grid = digits(EXAMPLE).unwrap().map(|digit| digit * 10);
+        grid[Point::new(1, 1)] = 0;
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![[10, 20, 30], [40, 0, 60]]
+        );
+    }
+}
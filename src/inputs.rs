@@ -0,0 +1,126 @@
+use std::env;
+use std::fs::{read_dir, read_to_string};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the inputs directory.
+pub const INPUTS_VAR: &str = "AOC_INPUTS";
+/// Environment variable overriding the layout of the files below the inputs directory.
+pub const LAYOUT_VAR: &str = "AOC_INPUT_LAYOUT";
+
+pub const DEFAULT_ROOT: &str = "inputs";
+/// Directory below the root with one root per additional account.
+pub const ACCOUNTS: &str = "accounts";
+pub const DEFAULT_LAYOUT: &str = "{year}/day{day}.txt";
+
+/// Locates the puzzle inputs below a root directory.
+///
+/// The layout is a relative path in which `{year}` is replaced by the year
+/// and `{day}` by the zero-padded day, e.g. `inputs/2024/day05.txt`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inputs {
+    pub root: PathBuf,
+    pub layout: String,
+}
+
+impl Default for Inputs {
+    fn default() -> Self {
+        Inputs::new(DEFAULT_ROOT)
+    }
+}
+
+impl Inputs {
+    pub fn new(root: impl Into<PathBuf>) -> Inputs {
+        Inputs {
+            root: root.into(),
+            layout: DEFAULT_LAYOUT.to_string(),
+        }
+    }
+
+    /// The default location, overridden by [`INPUTS_VAR`] and [`LAYOUT_VAR`] if set.
+    pub fn from_env() -> Inputs {
+        let mut inputs = env::var_os(INPUTS_VAR).map(Inputs::new).unwrap_or_default();
+        if let Ok(layout) = env::var(LAYOUT_VAR) {
+            inputs.layout = layout;
+        }
+        inputs
+    }
+
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        self.root.join(
+            self.layout
+                .replace("{year}", &year.to_string())
+                .replace("{day}", &format!("{:02}", day)),
+        )
+    }
+
+    pub fn read(&self, year: u32, day: u32) -> io::Result<String> {
+        read_to_string(self.path(year, day))
+    }
+
+    /// Path of a file kept next to the inputs, such as the known answers.
+    pub fn file(&self, name: impl AsRef<Path>) -> PathBuf {
+        self.root.join(name)
+    }
+
+    /// The inputs of another account, with their own answers, in the same layout.
+    pub fn account(&self, name: &str) -> Inputs {
+        Inputs {
+            root: self.root.join(ACCOUNTS).join(name),
+            layout: self.layout.clone(),
+        }
+    }
+
+    /// Names of the additional accounts, in order.
+    pub fn accounts(&self) -> io::Result<Vec<String>> {
+        let entries = match read_dir(self.root.join(ACCOUNTS)) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+        let mut names = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                names.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uses_conventional_layout() {
+        assert_eq!(
+            Inputs::default().path(2024, 5),
+            PathBuf::from("inputs/2024/day05.txt")
+        );
+    }
+
+    #[test]
+    fn uses_custom_layout() {
+        let inputs = Inputs {
+            root: PathBuf::from("/tmp/aoc"),
+            layout: "{year}-{day}.in".into(),
+        };
+        assert_eq!(inputs.path(2015, 3), PathBuf::from("/tmp/aoc/2015-03.in"));
+    }
+
+    #[test]
+    fn keeps_accounts_apart() {
+        let account = Inputs::default().account("work");
+        assert_eq!(
+            account.path(2024, 5),
+            PathBuf::from("inputs/accounts/work/2024/day05.txt")
+        );
+        assert_eq!(
+            account.file("answers.txt"),
+            PathBuf::from("inputs/accounts/work/answers.txt")
+        );
+    }
+}
@@ -1,20 +1,71 @@
-pub mod year_2015 {
-    pub mod day01;
-    pub mod day02;
-    pub mod day03;
+pub mod answers;
+pub mod cycle;
+pub mod geometry;
+pub mod grid;
+pub mod inputs;
+pub mod md5;
+pub mod parallel;
+pub mod parsing;
+pub mod render;
+pub mod runner;
+pub mod search;
+pub mod sha256;
+mod solver;
+pub mod store;
+pub mod vm;
+
+pub use parsing::ParseError;
+pub use solver::{Solution, Solver};
+
+macro_rules! solutions {
+    ($($year:ident: $($day:ident),+;)+) => {
+        vec![$($(Solution::of::<$year::$day::Day>()),+),+]
+    };
+}
+
+// Declares the `year_<year>::day<day>` modules found in `src` and `solutions()`, see `build.rs`.
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+// The template as `aoc new` fills it in, so that the build gates catch a template that does not
+// compile cleanly.
+#[cfg(test)]
+mod template {
+    include!(concat!(env!("OUT_DIR"), "/template.rs"));
 }
 
-pub mod year_2024 {
-    pub mod day01;
-    pub mod day02;
-    pub mod day03;
-    pub mod day04;
-    pub mod day05;
-    pub mod day06;
-    pub mod day07;
-    pub mod day08;
-    pub mod day09;
-    pub mod day10;
-    pub mod day11;
-    pub mod day12;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_solutions_in_order() {
+        let keys = solutions()
+            .iter()
+            .map(|solution| (solution.year, solution.day))
+            .collect::<Vec<_>>();
+        let mut sorted = keys.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(keys, sorted);
+    }
+
+    #[test]
+    fn runs_registered_solution() {
+        let solution = solutions()
+            .into_iter()
+            .find(|solution| (solution.year, solution.day) == (2015, 1))
+            .expect("2015 day 1 registered");
+        assert_eq!(
+            solution.run("()())"),
+            Ok(("-1".to_string(), "5".to_string()))
+        );
+    }
+
+    #[test]
+    fn runs_template() {
+        assert_eq!(
+            Solution::of::<template::Day>().run("input"),
+            Ok(("-".to_string(), "-".to_string()))
+        );
+    }
 }
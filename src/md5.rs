@@ -0,0 +1,320 @@
+//! MD5 as specified in RFC 1321, which several puzzles mine for hashes with a given prefix.
+
+use crate::parallel;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Left rotation of each round, four per group of sixteen steps.
+const SHIFTS: [u32; 16] = [7, 12, 17, 22, 5, 9, 14, 20, 4, 11, 16, 23, 6, 10, 15, 21];
+
+/// `floor(abs(sin(i + 1)) * 2^32)` for every step `i`.
+const SINES: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+/// Message word that each step adds.
+const INDEXES: [usize; 64] = {
+    let mut indexes = [0; 64];
+    let mut step = 0;
+    while step < 64 {
+        indexes[step] = match step / 16 {
+            0 => step,
+            1 => (5 * step + 1) % 16,
+            2 => (3 * step + 5) % 16,
+            _ => (7 * step) % 16,
+        };
+        step += 1;
+    }
+    indexes
+};
+
+const INITIAL: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+/// Nonces a worker of [`find_nonce`] claims at a time.
+const BATCH: u64 = 1 << 9;
+
+/// Incremental MD5 over data given in parts.
+///
+/// Cloning a hasher that has absorbed a common prefix hashes many messages with that prefix
+/// without compressing the prefix again, which is what mining for hashes needs.
+#[derive(Clone, Debug)]
+pub struct Md5 {
+    state: [u32; 4],
+    /// Data of an incomplete block, waiting for more.
+    buffer: [u8; 64],
+    buffered: usize,
+    /// Bytes absorbed so far.
+    length: u64,
+}
+
+impl Default for Md5 {
+    fn default() -> Md5 {
+        Md5::new()
+    }
+}
+
+impl Md5 {
+    pub fn new() -> Md5 {
+        Md5 {
+            state: INITIAL,
+            buffer: [0; 64],
+            buffered: 0,
+            length: 0,
+        }
+    }
+
+    /// Appends `data` to the message.
+    pub fn update(&mut self, mut data: &[u8]) -> &mut Md5 {
+        self.length += data.len() as u64;
+        if self.buffered > 0 {
+            let taken = data.len().min(64 - self.buffered);
+            self.buffer[self.buffered..self.buffered + taken].copy_from_slice(&data[..taken]);
+            self.buffered += taken;
+            data = &data[taken..];
+            if self.buffered < 64 {
+                return self;
+            }
+            compress(&mut self.state, &self.buffer);
+            self.buffered = 0;
+        }
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks {
+            compress(&mut self.state, block.try_into().expect("64 byte block"));
+        }
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+        self
+    }
+
+    /// The digest of the message.
+    pub fn finish(&self) -> [u8; 16] {
+        // The message is padded with a single 1 bit, zeros and its length in bits, in place
+        // because mining hashes little more than this last block.
+        let mut state = self.state;
+        let mut block = self.buffer;
+        block[self.buffered] = 0x80;
+        block[self.buffered + 1..].fill(0);
+        if self.buffered >= 56 {
+            compress(&mut state, &block);
+            block = [0; 64];
+        }
+        block[56..].copy_from_slice(&(self.length * 8).to_le_bytes());
+        compress(&mut state, &block);
+
+        let mut digest = [0; 16];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(state) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        digest
+    }
+}
+
+/// The 16 byte digest of `data`.
+pub fn digest(data: &[u8]) -> [u8; 16] {
+    Md5::new().update(data).finish()
+}
+
+/// The digest of `data` as lowercase hexadecimal.
+pub fn hex_digest(data: &[u8]) -> String {
+    hex(&digest(data))
+}
+
+/// A digest as lowercase hexadecimal.
+pub fn hex(digest: &[u8; 16]) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// The lowest nonce in `nonces` whose decimal digits appended to `prefix` hash to a digest that
+/// `accept`s.
+///
+/// The prefix is absorbed once. With the `parallel` feature a worker per core claims batches of
+/// nonces in order from a shared counter, and stops at the first batch that starts above the
+/// lowest match found so far. Every batch below it is searched to the end, so the result does
+/// not depend on the number of cores.
+pub fn find_nonce(
+    prefix: &[u8],
+    nonces: Range<u64>,
+    accept: impl Fn(&[u8; 16]) -> bool + Sync,
+) -> Option<u64> {
+    let mut hasher = Md5::new();
+    hasher.update(prefix);
+    let next_batch = AtomicU64::new(0);
+    let lowest = AtomicU64::new(u64::MAX);
+    parallel::workers(|| {
+        let mut digits = [0; 20];
+        loop {
+            let batch = next_batch.fetch_add(1, Ordering::Relaxed);
+            let Some(start) = batch
+                .checked_mul(BATCH)
+                .and_then(|offset| nonces.start.checked_add(offset))
+                .filter(|start| *start < nonces.end && *start < lowest.load(Ordering::Relaxed))
+            else {
+                return;
+            };
+            let end = start.saturating_add(BATCH).min(nonces.end);
+            let found = (start..end).find(|nonce| {
+                let mut message = hasher.clone();
+                message.update(decimal(*nonce, &mut digits));
+                accept(&message.finish())
+            });
+            if let Some(nonce) = found {
+                lowest.fetch_min(nonce, Ordering::Relaxed);
+                return;
+            }
+        }
+    });
+    // Nonces are below the end of the range, so they are never `u64::MAX`.
+    Some(lowest.into_inner()).filter(|nonce| *nonce != u64::MAX)
+}
+
+/// The decimal digits of `number`, written to the end of `buffer`.
+fn decimal(mut number: u64, buffer: &mut [u8; 20]) -> &[u8] {
+    let mut start = buffer.len();
+    loop {
+        start -= 1;
+        buffer[start] = b'0' + (number % 10) as u8;
+        number /= 10;
+        if number == 0 {
+            return &buffer[start..];
+        }
+    }
+}
+
+/// Number of hexadecimal zeros the digest starts with.
+pub fn leading_zeros(digest: &[u8; 16]) -> u32 {
+    u128::from_be_bytes(*digest).leading_zeros() / 4
+}
+
+fn compress(state: &mut [u32; 4], block: &[u8; 64]) {
+    let mut words = [0u32; 16];
+    for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().expect("4 bytes"));
+    }
+
+    // One loop per round, so each mixes its own way without branching.
+    let [mut a, mut b, mut c, mut d] = *state;
+    let mut step = |mix: fn(u32, u32, u32) -> u32, step: usize| {
+        let rotated = a
+            .wrapping_add(mix(b, c, d))
+            .wrapping_add(SINES[step])
+            .wrapping_add(words[INDEXES[step]])
+            .rotate_left(SHIFTS[step / 16 * 4 + step % 4]);
+        (a, b, c, d) = (d, b.wrapping_add(rotated), b, c);
+    };
+    for i in 0..16 {
+        step(|b, c, d| (b & c) | (!b & d), i);
+    }
+    for i in 16..32 {
+        step(|b, c, d| (d & b) | (!d & c), i);
+    }
+    for i in 32..48 {
+        step(|b, c, d| b ^ c ^ d, i);
+    }
+    for i in 48..64 {
+        step(|b, c, d| c ^ (b | !d), i);
+    }
+    for (word, value) in state.iter_mut().zip([a, b, c, d]) {
+        *word = word.wrapping_add(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("", "d41d8cd98f00b204e9800998ecf8427e")]
+    #[case("a", "0cc175b9c0f1b6a831c399e269772661")]
+    #[case("abc", "900150983cd24fb0d6963f7d28e17f72")]
+    #[case("message digest", "f96b697d7cb7938d525a2f31aaf161d0")]
+    #[case("abcdefghijklmnopqrstuvwxyz", "c3fcd3d76192e4007dfb496cca67e13b")]
+    #[case(
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+        "d174ab98d277d9f5a5611c2c9f419d9f"
+    )]
+    #[case(
+        "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+        "57edf4a22be3c955ac49da2e2107b67a"
+    )]
+    fn hashes_test_vectors(#[case] message: &str, #[case] digest: &str) {
+        assert_eq!(hex_digest(message.as_bytes()), digest);
+        let mut hasher = Md5::new();
+        for byte in message.as_bytes() {
+            hasher.update(&[*byte]);
+        }
+        assert_eq!(hex(&hasher.finish()), digest);
+    }
+
+    #[test]
+    fn hashes_in_any_parts() {
+        let message = (0..200).map(|byte| byte as u8).collect::<Vec<_>>();
+        let expected = digest(&message);
+        for split in 0..=message.len() {
+            let mut hasher = Md5::new();
+            hasher.update(&message[..split]).update(&message[split..]);
+            assert_eq!(hasher.finish(), expected, "split at {}", split);
+        }
+    }
+
+    #[test]
+    fn reuses_prefix() {
+        let mut prefix = Md5::new();
+        prefix.update(b"message ");
+        let mut message = prefix.clone();
+        message.update(b"digest");
+        assert_eq!(hex(&message.finish()), "f96b697d7cb7938d525a2f31aaf161d0");
+        assert_eq!(prefix.finish(), digest(b"message "));
+    }
+
+    #[rstest]
+    #[case(0, "0")]
+    #[case(7, "7")]
+    #[case(609043, "609043")]
+    #[case(u64::MAX, "18446744073709551615")]
+    fn writes_decimals(#[case] number: u64, #[case] digits: &str) {
+        assert_eq!(decimal(number, &mut [0; 20]), digits.as_bytes());
+    }
+
+    #[test]
+    fn finds_nonce() {
+        let five_zeros = |digest: &[u8; 16]| leading_zeros(digest) >= 5;
+        assert_eq!(
+            find_nonce(b"abcdef", 600_000..700_000, five_zeros),
+            Some(609043)
+        );
+        assert_eq!(find_nonce(b"abcdef", 0..1000, five_zeros), None);
+        assert_eq!(find_nonce(b"abc", 5..5, |_| true), None);
+        assert_eq!(find_nonce(b"abc", 5..6, |_| true), Some(5));
+        assert_eq!(
+            find_nonce(b"abc", u64::MAX - 3..u64::MAX, |digest| digest[0] < 0x80),
+            (u64::MAX - 3..u64::MAX)
+                .find(|nonce| { digest(format!("abc{}", nonce).as_bytes())[0] < 0x80 })
+        );
+    }
+
+    #[test]
+    fn finds_lowest_of_many_nonces() {
+        // Several batches hold a match, and only the lowest counts.
+        let three_zeros = |digest: &[u8; 16]| leading_zeros(digest) >= 3;
+        assert_eq!(
+            find_nonce(b"abc", 0..100_000, three_zeros),
+            (0..100_000).find(|nonce| three_zeros(&digest(format!("abc{}", nonce).as_bytes())))
+        );
+    }
+
+    #[test]
+    fn counts_leading_zeros() {
+        assert_eq!(leading_zeros(&digest(b"abcdef609043")), 5);
+        assert_eq!(leading_zeros(&[0xff; 16]), 0);
+        assert_eq!(leading_zeros(&[0; 16]), 32);
+    }
+}
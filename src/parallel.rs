@@ -0,0 +1,105 @@
+//! Helpers for embarrassingly parallel loops.
+//!
+//! With the `parallel` feature the items are split over all cores using scoped threads, without it
+//! they are processed on the calling thread, so callers work the same either way.
+
+use std::iter::Sum;
+
+/// Applies `f` to every item, keeping the order of the items.
+pub fn map<T, R>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        let chunk_size = items.len().div_ceil(threads()).max(1);
+        let f = &f;
+        std::thread::scope(|scope| {
+            let workers = items
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+                .collect::<Vec<_>>();
+            workers
+                .into_iter()
+                .flat_map(|worker| {
+                    worker
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        })
+    }
+    #[cfg(not(feature = "parallel"))]
+    items.iter().map(f).collect()
+}
+
+/// Runs `f` once on every core and collects what the runs return.
+///
+/// For work that the workers share out among themselves, such as batches claimed from an atomic
+/// counter, so the threads are spawned once however many batches there are. Without the
+/// `parallel` feature `f` runs once, on the calling thread.
+pub fn workers<R: Send>(f: impl Fn() -> R + Sync) -> Vec<R> {
+    map(&vec![(); threads()], |_| f())
+}
+
+/// Number of threads the work is split over.
+fn threads() -> usize {
+    if cfg!(feature = "parallel") {
+        std::thread::available_parallelism().map_or(1, |threads| threads.get())
+    } else {
+        1
+    }
+}
+
+/// Number of items that satisfy `predicate`.
+pub fn count<T: Sync>(items: &[T], predicate: impl Fn(&T) -> bool + Sync) -> usize {
+    map(items, predicate)
+        .into_iter()
+        .filter(|matches| *matches)
+        .count()
+}
+
+/// Sum of `f` over all items.
+pub fn sum<T, R>(items: &[T], f: impl Fn(&T) -> R + Sync) -> R
+where
+    T: Sync,
+    R: Send + Sum,
+{
+    map(items, f).into_iter().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn keeps_order() {
+        let items = (0..1000).collect::<Vec<u64>>();
+        assert_eq!(
+            map(&items, |n| n * n),
+            items.iter().map(|n| n * n).collect::<Vec<_>>()
+        );
+        assert_eq!(map(&[] as &[u64], |n| *n), vec![]);
+    }
+
+    #[test]
+    fn counts_and_sums() {
+        let items = (1..=100).collect::<Vec<u64>>();
+        assert_eq!(count(&items, |n| n % 3 == 0), 33);
+        assert_eq!(sum(&items, |n| *n), 5050);
+    }
+
+    #[test]
+    fn runs_workers_that_share_work() {
+        let next = AtomicUsize::new(0);
+        let done = workers(|| {
+            std::iter::from_fn(|| Some(next.fetch_add(1, Ordering::Relaxed)))
+                .take_while(|item| *item < 1000)
+                .count()
+        });
+        assert_eq!(done.len(), threads());
+        assert_eq!(done.iter().sum::<usize>(), 1000);
+    }
+}
@@ -0,0 +1,135 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Invalid puzzle input, with the 1-based line and column where it was detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// An error at the start of `fragment`, which has to be a slice of `input`.
+    ///
+    /// Fragments from elsewhere are reported at the end of the input.
+    pub fn at(input: &str, fragment: &str, message: impl Into<String>) -> ParseError {
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| *offset <= input.len())
+            .unwrap_or(input.len());
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        ParseError::new(
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            message,
+        )
+    }
+
+    /// An error for input that ends before it is complete.
+    pub fn end(input: &str, message: impl Into<String>) -> ParseError {
+        ParseError::at(input, &input[input.len()..], message)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `fragment`, a slice of `input`, reporting its position if it is not a valid `T`.
+pub fn parse_at<T: FromStr>(input: &str, fragment: &str, what: &str) -> Result<T, ParseError> {
+    fragment.parse().map_err(|_| {
+        ParseError::at(
+            input,
+            fragment,
+            format!("expected {}, found {:?}", what, fragment),
+        )
+    })
+}
+
+/// Splits `fragment`, a slice of `input`, at the first `separator`.
+pub fn split_once_at<'a>(
+    input: &str,
+    fragment: &'a str,
+    separator: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    fragment.split_once(separator).ok_or_else(|| {
+        ParseError::at(
+            input,
+            fragment,
+            format!("expected {:?} in {:?}", separator, fragment),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "12 34\n56 x8\n";
+
+    #[test]
+    fn locates_fragment() {
+        let fragment = &INPUT[9..11];
+        assert_eq!(
+            ParseError::at(INPUT, fragment, "bad"),
+            ParseError::new(2, 4, "bad")
+        );
+        assert_eq!(
+            ParseError::at(INPUT, &INPUT[..2], "bad"),
+            ParseError::new(1, 1, "bad")
+        );
+    }
+
+    #[test]
+    fn locates_end_of_input() {
+        assert_eq!(
+            ParseError::end(INPUT, "truncated"),
+            ParseError::new(3, 1, "truncated")
+        );
+        assert_eq!(
+            ParseError::at(INPUT, "elsewhere", "bad"),
+            ParseError::new(3, 1, "bad")
+        );
+    }
+
+    #[test]
+    fn parses_numbers_at_position() {
+        let numbers = INPUT.split_whitespace().collect::<Vec<_>>();
+        assert_eq!(parse_at::<u32>(INPUT, numbers[2], "number"), Ok(56));
+        assert_eq!(
+            parse_at::<u32>(INPUT, numbers[3], "number")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 4: expected number, found \"x8\""
+        );
+    }
+
+    #[test]
+    fn splits_at_position() {
+        let line = INPUT.lines().nth(1).unwrap();
+        assert_eq!(split_once_at(INPUT, line, " "), Ok(("56", "x8")));
+        assert_eq!(
+            split_once_at(INPUT, line, "|"),
+            Err(ParseError::new(2, 1, "expected \"|\" in \"56 x8\""))
+        );
+    }
+}
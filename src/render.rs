@@ -0,0 +1,188 @@
+//! Draws grids with coloured overlays, to the terminal or to image files.
+
+use crate::geometry::Point;
+use crate::grid::Grid;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const GREY: Rgb = Rgb(128, 128, 128);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(60, 170, 60);
+    pub const BLUE: Rgb = Rgb(38, 139, 210);
+    pub const YELLOW: Rgb = Rgb(230, 200, 40);
+
+    /// A colour for the `index`-th of any number of things, with neighbouring indices far apart.
+    pub fn palette(index: usize) -> Rgb {
+        // Steps around the colour wheel by the golden angle.
+        let hue = (index as f64 * 0.618_033_988_75).fract() * 6.0;
+        let (value, saturation) = (230.0, 0.65);
+        let channel = |offset: f64| {
+            let distance = ((hue + offset) % 6.0 - 3.0).abs();
+            let amount = (distance - 1.0).clamp(0.0, 1.0);
+            (value * (1.0 - saturation * (1.0 - amount))) as u8
+        };
+        Rgb(channel(0.0), channel(4.0), channel(2.0))
+    }
+}
+
+/// A grid cell as drawn: a character with an optional background colour.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cell {
+    pub symbol: char,
+    pub colour: Option<Rgb>,
+}
+
+impl Cell {
+    /// Colour of the cell in an image, where uncoloured cells show whether they are empty.
+    fn pixel(&self) -> Rgb {
+        self.colour.unwrap_or(match self.symbol {
+            '.' | ' ' => Rgb::BLACK,
+            _ => Rgb::GREY,
+        })
+    }
+}
+
+/// A drawing of a grid with overlays.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Picture(Grid<Cell>);
+
+impl Picture {
+    pub fn new<T>(grid: &Grid<T>, mut symbol: impl FnMut(&T) -> char) -> Picture {
+        Picture(grid.map(|value| Cell {
+            symbol: symbol(value),
+            colour: None,
+        }))
+    }
+
+    /// Colours the cells at `points`, such as visited cells or a path.
+    pub fn paint(&mut self, points: impl IntoIterator<Item = Point>, colour: Rgb) -> &mut Self {
+        for point in points {
+            if let Some(cell) = self.0.get_mut(point) {
+                cell.colour = Some(colour);
+            }
+        }
+        self
+    }
+
+    /// Colours each region with its own colour from the [`Rgb::palette`].
+    pub fn paint_regions<R>(&mut self, regions: impl IntoIterator<Item = R>) -> &mut Self
+    where
+        R: IntoIterator<Item = Point>,
+    {
+        for (index, region) in regions.into_iter().enumerate() {
+            self.paint(region, Rgb::palette(index));
+        }
+        self
+    }
+
+    /// Replaces the character drawn at `point`.
+    pub fn label(&mut self, point: Point, symbol: char) -> &mut Self {
+        if let Some(cell) = self.0.get_mut(point) {
+            cell.symbol = symbol;
+        }
+        self
+    }
+
+    /// Text with ANSI escape codes for the background colours.
+    pub fn ansi(&self) -> String {
+        let mut text = String::new();
+        for row in self.0.rows() {
+            let mut current = None;
+            for cell in row {
+                if cell.colour != current {
+                    match cell.colour {
+                        Some(Rgb(r, g, b)) => write!(text, "\x1b[48;2;{};{};{}m", r, g, b),
+                        None => write!(text, "\x1b[0m"),
+                    }
+                    .expect("writing to a string");
+                    current = cell.colour;
+                }
+                text.push(cell.symbol);
+            }
+            if current.is_some() {
+                text.push_str("\x1b[0m");
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Binary PPM image with `scale` by `scale` pixels per cell.
+    pub fn ppm(&self, scale: usize) -> Vec<u8> {
+        let (width, height) = (self.0.width() * scale, self.0.height() * scale);
+        let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        image.reserve(width * height * 3);
+        for row in self.0.rows() {
+            for _ in 0..scale {
+                for cell in row {
+                    let Rgb(r, g, b) = cell.pixel();
+                    for _ in 0..scale {
+                        image.extend([r, g, b]);
+                    }
+                }
+            }
+        }
+        image
+    }
+}
+
+/// Writes `pictures` as numbered PPM frames into `directory`, creating it if needed.
+///
+/// The frames can be combined into an animation, for example with
+/// `ffmpeg -i frame%05d.ppm animation.gif`.
+pub fn write_frames(directory: &Path, pictures: &[Picture], scale: usize) -> io::Result<()> {
+    fs::create_dir_all(directory)?;
+    for (index, picture) in pictures.iter().enumerate() {
+        fs::write(
+            directory.join(format!("frame{:05}.ppm", index)),
+            picture.ppm(scale),
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picture() -> Picture {
+        let grid = Grid::parse("#.\n..\n", "a tile", Some).unwrap();
+        let mut picture = Picture::new(&grid, |tile| *tile);
+        picture
+            .paint([Point::new(1, 0), Point::new(1, 1)], Rgb::RED)
+            .label(Point::new(1, 1), '^');
+        picture
+    }
+
+    #[test]
+    fn draws_to_terminal() {
+        assert_eq!(picture().ansi(), "#.\n\x1b[48;2;220;50;47m.^\x1b[0m\n");
+    }
+
+    #[test]
+    fn draws_image() {
+        let image = picture().ppm(2);
+        let header = b"P6\n4 4\n255\n";
+        assert_eq!(&image[..header.len()], header);
+        let pixels = &image[header.len()..];
+        assert_eq!(pixels.len(), 4 * 4 * 3);
+        assert_eq!(&pixels[..6], [128, 128, 128, 128, 128, 128]);
+        assert_eq!(&pixels[6..12], [0; 6]);
+        assert_eq!(&pixels[pixels.len() - 3..], [220, 50, 47]);
+    }
+
+    #[test]
+    fn colours_regions_differently() {
+        let colours = (0..8).map(Rgb::palette).collect::<Vec<_>>();
+        for (index, colour) in colours.iter().enumerate() {
+            assert!(!colours[index + 1..].contains(colour));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::inputs::Inputs;
+use crate::{ParseError, Solution};
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Why a solution could not be run.
+#[derive(Debug)]
+pub enum Error {
+    Input(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Input(error) if error.kind() == io::ErrorKind::NotFound => {
+                write!(f, "missing input")
+            }
+            Error::Input(error) => write!(f, "unreadable input: {}", error),
+            Error::Parse(error) => write!(f, "invalid input: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Answers of one solution together with the time taken by each phase.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answers {
+    pub part1: String,
+    pub part2: String,
+    pub timings: Timings,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timings {
+    pub parse: Summary,
+    pub part1: Summary,
+    pub part2: Summary,
+}
+
+impl Timings {
+    /// Median time of all phases together.
+    pub fn total(&self) -> Duration {
+        self.parse.median + self.part1.median + self.part2.median
+    }
+}
+
+/// Spread of the durations measured for one phase over repeated runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Summary {
+    pub fn of(samples: &mut [Duration]) -> Summary {
+        assert!(!samples.is_empty(), "at least one sample");
+        samples.sort_unstable();
+        Summary {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Runs a solution `repeat` times, timing parsing and both parts separately.
+pub fn run(solution: &Solution, data: &str, repeat: usize) -> Result<Answers, ParseError> {
+    let mut answers = None;
+    let mut samples = [Vec::new(), Vec::new(), Vec::new()];
+    for _ in 0..repeat.max(1) {
+        let (input, parse) = timed(|| solution.parse(data));
+        let input = input?;
+        let (part1, time1) = timed(|| solution.part1(input.as_ref()));
+        let (part2, time2) = timed(|| solution.part2(input.as_ref()));
+        for (phase, time) in samples.iter_mut().zip([parse, time1, time2]) {
+            phase.push(time);
+        }
+        answers.get_or_insert((part1, part2));
+    }
+    let (part1, part2) = answers.expect("at least one run");
+    let [parse, time1, time2] = &mut samples;
+    Ok(Answers {
+        part1,
+        part2,
+        timings: Timings {
+            parse: Summary::of(parse),
+            part1: Summary::of(time1),
+            part2: Summary::of(time2),
+        },
+    })
+}
+
+/// Formats a duration with a unit that suits its magnitude.
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1 {
+        format!("{} ns", duration.as_nanos())
+    } else if micros < 1_000 {
+        format!("{} µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.2} ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
+
+fn timed<T>(phase: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = phase();
+    (result, start.elapsed())
+}
+
+/// Runs a solution on its input found in the inputs directory.
+pub fn run_with_input(
+    solution: &Solution,
+    inputs: &Inputs,
+    repeat: usize,
+) -> Result<Answers, Error> {
+    let data = inputs
+        .read(solution.year, solution.day)
+        .map_err(Error::Input)?;
+    run(solution, &data, repeat).map_err(Error::Parse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_samples() {
+        let mut samples = [5, 1, 4, 2, 3].map(Duration::from_millis);
+        assert_eq!(
+            Summary::of(&mut samples),
+            Summary {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            }
+        );
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(Duration::from_nanos(420)), "420 ns");
+        assert_eq!(format_duration(Duration::from_micros(999)), "999 µs");
+        assert_eq!(format_duration(Duration::from_micros(12_346)), "12.35 ms");
+        assert_eq!(format_duration(Duration::from_millis(2_500)), "2.50 s");
+    }
+
+    #[test]
+    fn keeps_answers_of_repeated_runs() {
+        let solution = crate::solutions()
+            .into_iter()
+            .find(|solution| (solution.year, solution.day) == (2015, 1))
+            .expect("2015 day 1 registered");
+        let answers = run(&solution, "()())", 3).unwrap();
+        assert_eq!(
+            (answers.part1.as_str(), answers.part2.as_str()),
+            ("-1", "5")
+        );
+    }
+}
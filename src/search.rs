@@ -0,0 +1,365 @@
+//! Searches over implicit graphs, given as a start state and a closure listing the successors of
+//! a state.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Every state reachable from `start`, including itself.
+pub fn reachable<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for next in successors(&node) {
+            if seen.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+    }
+    seen
+}
+
+/// Number of steps from `start` to every reachable state.
+pub fn distances<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Path with the fewest steps from `start` to the first state that satisfies `goal`.
+pub fn bfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        if goal(&node) {
+            return Some(path(node, |node| parents[node].clone()));
+        }
+        for next in successors(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Some path from `start` to a state that satisfies `goal`, exploring depth first.
+pub fn dfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::from([(start.clone(), None)]);
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if goal(&node) {
+            return Some(path(node, |node| parents[node].clone()));
+        }
+        for next in successors(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                stack.push(next);
+            }
+        }
+    }
+    None
+}
+
+/// Cheapest path from `start` to a state that satisfies `goal`, with its cost.
+///
+/// `successors` lists the next states together with the cost of moving there.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), goal)
+}
+
+/// Cost of the cheapest path from `start` to every reachable state.
+///
+/// The weighted counterpart of [`distances`], for finding all the cheapest paths rather than one.
+pub fn costs<N, C, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut queue = BinaryHeap::from([Reverse((C::default(), 0))]);
+    let mut costs = HashMap::from([(start.clone(), C::default())]);
+    let mut nodes = vec![start];
+    while let Some(Reverse((cost, index))) = queue.pop() {
+        let node = nodes[index].clone();
+        if costs[&node] < cost {
+            continue;
+        }
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|known| next_cost < *known) {
+                costs.insert(next.clone(), next_cost);
+                queue.push(Reverse((next_cost, nodes.len())));
+                nodes.push(next);
+            }
+        }
+    }
+    costs
+}
+
+/// Cheapest path like [`dijkstra`], guided by a `heuristic` that must never overestimate the
+/// remaining cost.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // The queue refers to states by their index in `nodes`, so they do not need to be `Ord`.
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut best = HashMap::from([(start.clone(), (C::default(), None))]);
+    let mut nodes = vec![start];
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        let node = nodes[index].clone();
+        if best[&node].0 < cost {
+            continue;
+        }
+        if goal(&node) {
+            return Some((path(node, |node| best[node].1.clone()), cost));
+        }
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if best.get(&next).is_none_or(|(known, _)| next_cost < *known) {
+                best.insert(next.clone(), (next_cost, Some(node.clone())));
+                queue.push(Reverse((
+                    next_cost + heuristic(&next),
+                    next_cost,
+                    nodes.len(),
+                )));
+                nodes.push(next);
+            }
+        }
+    }
+    None
+}
+
+/// Number of distinct paths from `start` to states that satisfy `goal`.
+///
+/// Paths stop at the first goal they reach, and the graph must not have cycles.
+pub fn count_paths<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> usize
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    fn count<N, I>(
+        node: &N,
+        successors: &mut dyn FnMut(&N) -> I,
+        goal: &mut dyn FnMut(&N) -> bool,
+        counts: &mut HashMap<N, usize>,
+    ) -> usize
+    where
+        N: Eq + Hash + Clone,
+        I: IntoIterator<Item = N>,
+    {
+        if let Some(count) = counts.get(node) {
+            return *count;
+        }
+        let total = if goal(node) {
+            1
+        } else {
+            successors(node)
+                .into_iter()
+                .map(|next| count(&next, successors, goal, counts))
+                .sum()
+        };
+        counts.insert(node.clone(), total);
+        total
+    }
+    count(&start, &mut successors, &mut goal, &mut HashMap::new())
+}
+
+/// Follows `parent` links back from `end` and returns the path from the start to `end`.
+fn path<N: Clone>(end: N, mut parent: impl FnMut(&N) -> Option<N>) -> Vec<N> {
+    let mut path = vec![end];
+    while let Some(previous) = parent(path.last().expect("non-empty path")) {
+        path.push(previous);
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Point;
+    use crate::grid::Grid;
+
+    const MAZE: &str = "\
+S.#....
+.##.##.
+...#..E
+.#...#.
+";
+
+    fn maze() -> Grid<char> {
+        Grid::parse(MAZE, "a maze tile", Some).unwrap()
+    }
+
+    fn open(maze: &Grid<char>, point: &Point) -> Vec<Point> {
+        point
+            .neighbours()
+            .filter(|next| maze.get(*next).is_some_and(|tile| *tile != '#'))
+            .collect()
+    }
+
+    #[test]
+    fn finds_reachable_states() {
+        let mut states = reachable(1, |n| [n * 2 % 10])
+            .into_iter()
+            .collect::<Vec<_>>();
+        states.sort();
+        assert_eq!(states, vec![1, 2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn measures_distances() {
+        let maze = maze();
+        let distances = distances(Point::ORIGIN, |point| open(&maze, point));
+        assert_eq!(distances[&Point::new(2, 6)], 10);
+        assert_eq!(distances.get(&Point::new(0, 3)), Some(&15));
+    }
+
+    #[test]
+    fn finds_shortest_path() {
+        let maze = maze();
+        let path = bfs(
+            Point::ORIGIN,
+            |point| open(&maze, point),
+            |point| maze[*point] == 'E',
+        )
+        .unwrap();
+        assert_eq!(path.len(), 11);
+        assert_eq!(path.first(), Some(&Point::ORIGIN));
+        assert_eq!(path.last(), Some(&Point::new(2, 6)));
+        assert!(path.windows(2).all(|step| step[0].manhattan(step[1]) == 1));
+        assert_eq!(bfs(0, |n| [(n + 2) % 10], |n| *n == 5), None);
+    }
+
+    #[test]
+    fn finds_some_path() {
+        let maze = maze();
+        let path = dfs(
+            Point::ORIGIN,
+            |point| open(&maze, point),
+            |point| maze[*point] == 'E',
+        )
+        .unwrap();
+        assert_eq!(path.first(), Some(&Point::ORIGIN));
+        assert_eq!(path.last(), Some(&Point::new(2, 6)));
+        assert!(path.windows(2).all(|step| step[0].manhattan(step[1]) == 1));
+    }
+
+    #[test]
+    fn finds_cheapest_path() {
+        let edges = HashMap::from([
+            ('a', vec![('b', 7), ('c', 9), ('f', 14)]),
+            ('b', vec![('c', 10), ('d', 15)]),
+            ('c', vec![('d', 11), ('f', 2)]),
+            ('d', vec![('e', 6)]),
+            ('f', vec![('e', 9)]),
+        ]);
+        let successors = |node: &char| edges.get(node).cloned().unwrap_or_default();
+        assert_eq!(
+            dijkstra('a', successors, |node| *node == 'e'),
+            Some((vec!['a', 'c', 'f', 'e'], 20))
+        );
+        assert_eq!(dijkstra('e', successors, |node| *node == 'a'), None);
+    }
+
+    #[test]
+    fn measures_costs() {
+        let edges = HashMap::from([
+            ('a', vec![('b', 7), ('c', 9), ('f', 14)]),
+            ('b', vec![('c', 10), ('d', 15)]),
+            ('c', vec![('d', 11), ('f', 2)]),
+            ('d', vec![('e', 6)]),
+            ('f', vec![('e', 9)]),
+        ]);
+        let costs = costs('a', |node| edges.get(node).cloned().unwrap_or_default());
+        assert_eq!(costs[&'a'], 0);
+        assert_eq!(costs[&'d'], 20);
+        assert_eq!(costs[&'e'], 20);
+        assert_eq!(costs[&'f'], 11);
+        assert_eq!(costs.len(), 6);
+    }
+
+    #[test]
+    fn finds_cheapest_path_with_heuristic() {
+        let maze = maze();
+        let end = Point::new(2, 6);
+        let (path, cost) = astar(
+            Point::ORIGIN,
+            |point| open(&maze, point).into_iter().map(|next| (next, 1)),
+            |point| point.manhattan(end),
+            |point| *point == end,
+        )
+        .unwrap();
+        assert_eq!((path.len(), cost), (11, 10));
+    }
+
+    #[test]
+    fn counts_paths() {
+        let paths = count_paths(
+            Point::ORIGIN,
+            |point| {
+                [*point + Point::new(1, 0), *point + Point::new(0, 1)]
+                    .into_iter()
+                    .filter(|next| next.row <= 2 && next.column <= 3)
+            },
+            |point| *point == Point::new(2, 3),
+        );
+        assert_eq!(paths, 10);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::render::Picture;
+use crate::ParseError;
+use std::any::Any;
+use std::fmt::Display;
+
+/// A puzzle solution for a single day.
+///
+/// The input is parsed once and shared by both parts.
+pub trait Solver {
+    const YEAR: u32;
+    const DAY: u32;
+    /// Number of parts the puzzle has, the last day of a year has only one.
+    const PARTS: u8 = 2;
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> impl Display;
+    fn part2(input: &Self::Input) -> impl Display;
+
+    /// Pictures of how the solution works, in order, for days that can draw themselves.
+    fn visualise(_input: &Self::Input) -> Vec<Picture> {
+        Vec::new()
+    }
+}
+
+/// A type-erased [`Solver`] as stored in the registry.
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    pub parts: u8,
+    parse: fn(&str) -> Result<Box<dyn Any>, ParseError>,
+    part1: fn(&dyn Any) -> String,
+    part2: fn(&dyn Any) -> String,
+    visualise: fn(&dyn Any) -> Vec<Picture>,
+}
+
+impl Solution {
+    pub fn of<S: Solver>() -> Self
+    where
+        S::Input: 'static,
+    {
+        Solution {
+            year: S::YEAR,
+            day: S::DAY,
+            parts: S::PARTS,
+            parse: |data| Ok(Box::new(S::parse(data)?)),
+            part1: |input| S::part1(downcast::<S>(input)).to_string(),
+            part2: |input| S::part2(downcast::<S>(input)).to_string(),
+            visualise: |input| S::visualise(downcast::<S>(input)),
+        }
+    }
+
+    pub fn parse(&self, data: &str) -> Result<Box<dyn Any>, ParseError> {
+        (self.parse)(data)
+    }
+
+    pub fn part1(&self, input: &dyn Any) -> String {
+        (self.part1)(input)
+    }
+
+    pub fn part2(&self, input: &dyn Any) -> String {
+        (self.part2)(input)
+    }
+
+    pub fn visualise(&self, input: &dyn Any) -> Vec<Picture> {
+        (self.visualise)(input)
+    }
+
+    pub fn run(&self, data: &str) -> Result<(String, String), ParseError> {
+        let input = self.parse(data)?;
+        Ok((self.part1(input.as_ref()), self.part2(input.as_ref())))
+    }
+}
+
+fn downcast<S: Solver>(input: &dyn Any) -> &S::Input
+where
+    S::Input: 'static,
+{
+    input
+        .downcast_ref::<S::Input>()
+        .expect("input parsed by the same solution")
+}
+
+/// Implements [`Solver`] for the `parse`, `part1` and `part2` functions of the calling module.
+///
+/// With `parts = 1` only `parse` and `part1` are needed. With `visualise` the module also
+/// provides a `visualise` function that draws the solution.
+#[macro_export]
+macro_rules! solver {
+    ($year:literal, $day:literal, $input:ty) => {
+        $crate::solver!(@impl $year, $day, $input, 2, part2, {});
+    };
+    ($year:literal, $day:literal, $input:ty, visualise) => {
+        $crate::solver!(@impl $year, $day, $input, 2, part2, {
+            fn visualise(input: &Self::Input) -> Vec<$crate::render::Picture> {
+                visualise(input)
+            }
+        });
+    };
+    ($year:literal, $day:literal, $input:ty, parts = 1) => {
+        fn no_part2(_: &$input) -> &'static str {
+            $crate::answers::NO_ANSWER
+        }
+
+        $crate::solver!(@impl $year, $day, $input, 1, no_part2, {});
+    };
+    (
+        @impl $year:literal,
+        $day:literal,
+        $input:ty,
+        $parts:literal,
+        $part2:ident,
+        { $($visualise:tt)* }
+    ) => {
+        pub struct Day;
+
+        impl $crate::Solver for Day {
+            const YEAR: u32 = $year;
+            const DAY: u32 = $day;
+            const PARTS: u8 = $parts;
+
+            type Input = $input;
+
+            fn parse(input: &str) -> Result<Self::Input, $crate::ParseError> {
+                parse(input)
+            }
+
+            fn part1(input: &Self::Input) -> impl std::fmt::Display {
+                part1(input)
+            }
+
+            fn part2(input: &Self::Input) -> impl std::fmt::Display {
+                $part2(input)
+            }
+
+            $($visualise)*
+        }
+    };
+}
@@ -0,0 +1,346 @@
+//! A local store of puzzle inputs in the canonical layout, with a checksum per input.
+//!
+//! Importing an input normalises its line endings and records its length and SHA-256, so inputs
+//! that were cut short or edited afterwards can be told apart from the ones that were downloaded.
+
+use crate::inputs::Inputs;
+use crate::sha256;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// File with the checksums, next to the inputs they belong to.
+pub const CHECKSUMS: &str = "checksums.txt";
+
+/// Length and SHA-256 of an input when it was imported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checksum {
+    pub length: usize,
+    pub sha256: String,
+}
+
+impl Checksum {
+    pub fn of(data: &str) -> Checksum {
+        Checksum {
+            length: data.len(),
+            sha256: sha256::hex_digest(data.as_bytes()),
+        }
+    }
+}
+
+/// Checksums keyed by year and day.
+///
+/// Stored as one input per line: `<year> <day> <length> <sha256>`.
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Default, PartialEq)]
+pub struct Checksums {
+    checksums: BTreeMap<(u32, u32), Checksum>,
+}
+
+/// Something wrong with a stored input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    Empty,
+    MissingNewline,
+    CarriageReturns,
+    /// The last line is shorter than all others, which are equally long, as in a cut off grid.
+    ShortLastLine {
+        expected: usize,
+        found: usize,
+    },
+    /// Shorter than when it was imported.
+    Truncated {
+        expected: usize,
+        found: usize,
+    },
+    /// Different from when it was imported.
+    Modified,
+    /// Not imported, so there is nothing to compare it with.
+    Unrecorded,
+}
+
+impl Checksums {
+    /// Loads the checksums from a file, a missing file has no checksums.
+    pub fn load(path: &Path) -> io::Result<Checksums> {
+        match fs::read_to_string(path) {
+            Ok(content) => content
+                .parse()
+                .map_err(|error| io::Error::new(ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Checksums::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Option<&Checksum> {
+        self.checksums.get(&(year, day))
+    }
+
+    pub fn insert(&mut self, year: u32, day: u32, checksum: Checksum) {
+        self.checksums.insert((year, day), checksum);
+    }
+
+    /// The years and days with a checksum, in order.
+    pub fn days(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.checksums.keys().copied()
+    }
+}
+
+impl std::str::FromStr for Checksums {
+    type Err = String;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let mut checksums = BTreeMap::new();
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |what| format!("line {}: invalid {}", number + 1, what);
+            if let [year, day, length, sha256] = line.split_whitespace().collect::<Vec<_>>()[..] {
+                let key = (
+                    year.parse().map_err(|_| invalid("year"))?,
+                    day.parse().map_err(|_| invalid("day"))?,
+                );
+                let checksum = Checksum {
+                    length: length.parse().map_err(|_| invalid("length"))?,
+                    sha256: sha256.to_string(),
+                };
+                checksums.insert(key, checksum);
+            } else {
+                return Err(invalid("checksum line"));
+            }
+        }
+        Ok(Checksums { checksums })
+    }
+}
+
+impl Display for Checksums {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for ((year, day), Checksum { length, sha256 }) in &self.checksums {
+            writeln!(f, "{} {:02} {} {}", year, day, length, sha256)?;
+        }
+        Ok(())
+    }
+}
+
+impl Problem {
+    /// Whether [`normalise`] fixes the problem, the others stay in a stored input.
+    pub fn is_normalised(&self) -> bool {
+        matches!(self, Problem::MissingNewline | Problem::CarriageReturns)
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::Empty => write!(f, "empty"),
+            Problem::MissingNewline => write!(f, "no newline at the end"),
+            Problem::CarriageReturns => write!(f, "Windows line endings"),
+            Problem::ShortLastLine { expected, found } => write!(
+                f,
+                "last line has {} characters instead of {}",
+                found, expected
+            ),
+            Problem::Truncated { expected, found } => {
+                write!(f, "truncated to {} of {} bytes", found, expected)
+            }
+            Problem::Modified => write!(f, "modified since import"),
+            Problem::Unrecorded => write!(f, "not imported"),
+        }
+    }
+}
+
+/// Problems that show in the content of an input itself.
+pub fn inspect(data: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    if data.trim().is_empty() {
+        problems.push(Problem::Empty);
+        return problems;
+    }
+    if data.contains('\r') {
+        problems.push(Problem::CarriageReturns);
+    }
+    if !data.ends_with('\n') {
+        problems.push(Problem::MissingNewline);
+    }
+    let lengths = data.lines().map(str::len).collect::<Vec<_>>();
+    if let [first, .., last] = lengths[..] {
+        let rest = &lengths[..lengths.len() - 1];
+        if last < first && rest.len() > 1 && rest.iter().all(|length| *length == first) {
+            problems.push(Problem::ShortLastLine {
+                expected: first,
+                found: last,
+            });
+        }
+    }
+    problems
+}
+
+/// Problems of a stored input, including differences from the checksum recorded on import.
+pub fn verify(data: &str, checksum: Option<&Checksum>) -> Vec<Problem> {
+    let mut problems = inspect(data);
+    match checksum {
+        None => problems.push(Problem::Unrecorded),
+        Some(checksum) if data.len() < checksum.length => problems.push(Problem::Truncated {
+            expected: checksum.length,
+            found: data.len(),
+        }),
+        Some(checksum) if *checksum != Checksum::of(data) => problems.push(Problem::Modified),
+        Some(_) => {}
+    }
+    problems
+}
+
+/// The input with Unix line endings and a newline at the end.
+pub fn normalise(data: &str) -> String {
+    let mut normalised = data.replace("\r\n", "\n");
+    if !normalised.ends_with('\n') {
+        normalised.push('\n');
+    }
+    normalised
+}
+
+/// Stores an input in the canonical location and records its checksum.
+///
+/// Returns the problems found in `data` before it was normalised. An input that is already stored
+/// is only replaced by the same content, so a damaged copy cannot overwrite a good one.
+pub fn import(inputs: &Inputs, year: u32, day: u32, data: &str) -> io::Result<Vec<Problem>> {
+    let problems = inspect(data);
+    if problems.contains(&Problem::Empty) {
+        return Err(io::Error::new(ErrorKind::InvalidData, "the input is empty"));
+    }
+    let data = normalise(data);
+    let path = inputs.path(year, day);
+    match fs::read_to_string(&path) {
+        Ok(stored) if stored != data => {
+            return Err(io::Error::new(
+                ErrorKind::AlreadyExists,
+                format!("{} differs, remove it to replace it", path.display()),
+            ))
+        }
+        Ok(_) => {}
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            if let Some(directory) = path.parent() {
+                fs::create_dir_all(directory)?;
+            }
+            fs::write(&path, &data)?;
+        }
+        Err(error) => return Err(error),
+    }
+
+    let checksums_path = inputs.file(CHECKSUMS);
+    let mut checksums = Checksums::load(&checksums_path)?;
+    checksums.insert(year, day, Checksum::of(&data));
+    checksums.save(&checksums_path)?;
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "\
+# Recorded on import
+2024 01 14 35bec5d6e8e0bd8ba2b0dce00b08f3f5e02a1ae28d4a7a5bb5a5e68c0b4c2c8a
+2015 25 3 0263829989b6fd954f72baaf2fc64bc2e2f01d692d4de72986ea808f6e99813f
+";
+
+    #[test]
+    fn parses_checksums() {
+        let checksums = EXAMPLE.parse::<Checksums>().unwrap();
+        assert_eq!(
+            checksums.get(2015, 25).map(|checksum| checksum.length),
+            Some(3)
+        );
+        assert_eq!(checksums.get(2024, 2), None);
+        assert_eq!(
+            checksums.days().collect::<Vec<_>>(),
+            [(2015, 25), (2024, 1)]
+        );
+        assert_eq!(checksums.to_string().parse(), Ok(checksums));
+    }
+
+    #[test]
+    fn rejects_invalid_checksums() {
+        assert_eq!(
+            "2024 01 many abc".parse::<Checksums>(),
+            Err("line 1: invalid length".to_string())
+        );
+        assert_eq!(
+            "2024 01 14".parse::<Checksums>(),
+            Err("line 1: invalid checksum line".to_string())
+        );
+    }
+
+    #[rstest]
+    #[case("3   4\n4   3\n", vec![])]
+    #[case("", vec![Problem::Empty])]
+    #[case("\n\n", vec![Problem::Empty])]
+    #[case("3   4\n4   3", vec![Problem::MissingNewline])]
+    #[case("3   4\r\n4   3\r\n", vec![Problem::CarriageReturns])]
+    #[case(
+        "#..\n...\n.#",
+        vec![Problem::MissingNewline, Problem::ShortLastLine { expected: 3, found: 2 }]
+    )]
+    #[case("p=0,4 v=3,-3\np=6,3 v=-1,-3\n", vec![])]
+    fn inspects_content(#[case] data: &str, #[case] problems: Vec<Problem>) {
+        assert_eq!(inspect(data), problems);
+    }
+
+    #[test]
+    fn compares_with_checksum() {
+        let checksum = Checksum::of("3   4\n4   3\n");
+        assert_eq!(verify("3   4\n4   3\n", Some(&checksum)), vec![]);
+        assert_eq!(
+            verify("3   4\n4", Some(&checksum)),
+            vec![
+                Problem::MissingNewline,
+                Problem::Truncated {
+                    expected: 12,
+                    found: 7
+                }
+            ]
+        );
+        assert_eq!(
+            verify("3   4\n4   4\n", Some(&checksum)),
+            vec![Problem::Modified]
+        );
+        assert_eq!(verify("3   4\n4   3\n", None), vec![Problem::Unrecorded]);
+    }
+
+    #[test]
+    fn normalises_line_endings() {
+        assert_eq!(normalise("a\r\nb"), "a\nb\n");
+        assert_eq!(normalise("a\nb\n"), "a\nb\n");
+    }
+
+    #[test]
+    fn tells_which_problems_are_normalised() {
+        let fixed = inspect("abc\r\nabc\r\nab")
+            .into_iter()
+            .map(|problem| (problem.is_normalised(), problem))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fixed,
+            [
+                (true, Problem::CarriageReturns),
+                (true, Problem::MissingNewline),
+                (
+                    false,
+                    Problem::ShortLastLine {
+                        expected: 3,
+                        found: 2
+                    }
+                ),
+            ]
+        );
+    }
+}
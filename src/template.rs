@@ -1,14 +1,37 @@
+use crate::ParseError;
+
+crate::solver!(YEAR, DAY, Input);
 
 type Input = String;
 
-pub fn part1(input: &Input) -> String {
-    todo!()
+pub fn part1(_input: &Input) -> String {
+    crate::answers::NO_ANSWER.to_string()
+}
+
+pub fn part2(_input: &Input) -> String {
+    crate::answers::NO_ANSWER.to_string()
 }
 
-pub fn part2(input: &Input) -> String {
-    todo!()
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    Ok(input.to_string())
 }
 
-pub fn parse(input: &str) -> Input {
-    todo!()
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+";
+
+    #[test]
+    #[ignore = "example not filled in yet"]
+    fn solves_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), "");
+    }
+
+    #[test]
+    #[ignore = "example not filled in yet"]
+    fn solves_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), "");
+    }
+}
@@ -0,0 +1,522 @@
+//! A register machine for the puzzles that run programs in a small assembly language.
+//!
+//! Each puzzle defines its instruction set as a type implementing [`Instruction`], and a
+//! [`Machine`] runs a program of them with a program counter, registers and output, stopping when
+//! the program counter leaves the program, at breakpoints or after a number of steps.
+//!
+//! For puzzles whose answer depends on what a program does, [`disassemble`] lists a program with
+//! its jumps and loops, and [`solve_backwards`] finds register values that make a loop output a
+//! given sequence.
+
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Display, Formatter, Write};
+
+/// One instruction of a puzzle's instruction set.
+pub trait Instruction: Clone + Debug {
+    /// Contents of a register, which is also what the program outputs.
+    type Value: Copy + Debug + PartialEq;
+
+    /// Executes the instruction on the registers and says where the program continues.
+    fn execute(&self, registers: &mut [Self::Value], output: &mut Vec<Self::Value>) -> Flow;
+
+    /// Where the program continues if the instruction jumps, for the instructions that can.
+    fn branch(&self) -> Option<Flow> {
+        None
+    }
+}
+
+/// Where the program continues after an instruction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Flow {
+    /// The instruction after this one.
+    Next,
+    /// An offset from this instruction.
+    Jump(isize),
+    /// The instruction at an index in the program.
+    Goto(usize),
+    Halt,
+}
+
+/// Why [`Machine::run`] returned.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Stop {
+    /// The program counter left the program or an instruction halted it.
+    Halted,
+    /// The next instruction is at a breakpoint.
+    Breakpoint(usize),
+    /// The machine has taken as many steps as its limit allows.
+    Limit,
+}
+
+/// A backward jump from the instruction at `end` to the one at `start`, which repeats the
+/// instructions in between.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Loop {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// An executed instruction, with the registers after it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Step<I: Instruction> {
+    pub pc: usize,
+    pub instruction: I,
+    pub registers: Vec<I::Value>,
+}
+
+impl<I: Instruction> Display for Step<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>4}  {:<24}  {:?}",
+            self.pc,
+            format!("{:?}", self.instruction),
+            self.registers
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Machine<'a, I: Instruction> {
+    program: &'a [I],
+    registers: Vec<I::Value>,
+    output: Vec<I::Value>,
+    /// Index of the next instruction, `None` once the program has halted.
+    pc: Option<usize>,
+    steps: usize,
+    limit: Option<usize>,
+    breakpoints: BTreeSet<usize>,
+    trace: Option<Vec<Step<I>>>,
+}
+
+impl<'a, I: Instruction> Machine<'a, I> {
+    /// A machine at the first instruction of `program`.
+    pub fn new(program: &'a [I], registers: impl Into<Vec<I::Value>>) -> Machine<'a, I> {
+        Machine {
+            program,
+            registers: registers.into(),
+            output: Vec::new(),
+            pc: (!program.is_empty()).then_some(0),
+            steps: 0,
+            limit: None,
+            breakpoints: BTreeSet::new(),
+            trace: None,
+        }
+    }
+
+    /// Stops [`Machine::run`] before the instructions at these indices.
+    pub fn with_breakpoints(mut self, breakpoints: impl IntoIterator<Item = usize>) -> Self {
+        self.breakpoints.extend(breakpoints);
+        self
+    }
+
+    /// Stops [`Machine::run`] once the machine has taken this many steps in total, as a guard
+    /// against programs that never halt.
+    pub fn with_limit(mut self, steps: usize) -> Self {
+        self.limit = Some(steps);
+        self
+    }
+
+    /// Records every executed instruction, see [`Machine::trace`].
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(Vec::new());
+        self
+    }
+
+    pub fn registers(&self) -> &[I::Value] {
+        &self.registers
+    }
+
+    pub fn registers_mut(&mut self) -> &mut [I::Value] {
+        &mut self.registers
+    }
+
+    pub fn output(&self) -> &[I::Value] {
+        &self.output
+    }
+
+    /// Index of the next instruction, `None` once the program has halted.
+    pub fn pc(&self) -> Option<usize> {
+        self.pc
+    }
+
+    /// Number of instructions executed so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// The executed instructions, if the machine was created [`Machine::with_trace`].
+    pub fn trace(&self) -> Option<&[Step<I>]> {
+        self.trace.as_deref()
+    }
+
+    /// Executes the next instruction, returning `false` if the program has halted.
+    pub fn step(&mut self) -> bool {
+        let Some(pc) = self.pc else {
+            return false;
+        };
+        let instruction = &self.program[pc];
+        let flow = instruction.execute(&mut self.registers, &mut self.output);
+        self.pc = target(pc, flow).filter(|next| *next < self.program.len());
+        self.steps += 1;
+        if let Some(trace) = &mut self.trace {
+            trace.push(Step {
+                pc,
+                instruction: instruction.clone(),
+                registers: self.registers.clone(),
+            });
+        }
+        true
+    }
+
+    /// Executes instructions until the program halts, reaches a breakpoint or the step limit.
+    ///
+    /// A breakpoint at the very next instruction does not stop the machine, so calling `run`
+    /// again continues after a breakpoint.
+    pub fn run(&mut self) -> Stop {
+        let mut first = true;
+        loop {
+            match self.pc {
+                None => return Stop::Halted,
+                Some(pc) if !first && self.breakpoints.contains(&pc) => {
+                    return Stop::Breakpoint(pc)
+                }
+                _ if self.limit.is_some_and(|limit| self.steps >= limit) => return Stop::Limit,
+                _ => {}
+            }
+            self.step();
+            first = false;
+        }
+    }
+}
+
+/// Index of the instruction that `flow` leads to from the one at `pc`, `None` for a halt or a jump
+/// before the first instruction.
+fn target(pc: usize, flow: Flow) -> Option<usize> {
+    match flow {
+        Flow::Next => pc.checked_add(1),
+        Flow::Jump(offset) => pc.checked_add_signed(offset),
+        Flow::Goto(index) => Some(index),
+        Flow::Halt => None,
+    }
+}
+
+/// The jumps of a program that go back to an earlier or the same instruction, outermost first.
+pub fn loops<I: Instruction>(program: &[I]) -> Vec<Loop> {
+    let mut loops = program
+        .iter()
+        .enumerate()
+        .filter_map(|(end, instruction)| {
+            let start = target(end, instruction.branch()?)?;
+            (start <= end).then_some(Loop { start, end })
+        })
+        .collect::<Vec<_>>();
+    loops.sort_by_key(|repeated| (repeated.start, std::cmp::Reverse(repeated.end)));
+    loops
+}
+
+/// Lists a program one instruction per line with its index, noting where each jump goes and
+/// where it comes from.
+///
+/// The loops are drawn in the margin, one column per loop from the outermost on the left.
+pub fn disassemble<I: Instruction + Display>(program: &[I]) -> String {
+    let loops = loops(program);
+    let targets = program
+        .iter()
+        .enumerate()
+        .map(|(pc, instruction)| {
+            let flow = instruction.branch()?;
+            Some(target(pc, flow).filter(|target| *target < program.len()))
+        })
+        .collect::<Vec<_>>();
+    let listings = program.iter().map(ToString::to_string).collect::<Vec<_>>();
+    let width = listings.iter().map(String::len).max().unwrap_or(0);
+    let digits = program.len().saturating_sub(1).to_string().len();
+
+    let mut listing = String::new();
+    for (pc, instruction) in listings.iter().enumerate() {
+        let margin = loops
+            .iter()
+            .map(|repeated| match pc {
+                _ if pc == repeated.start && pc == repeated.end => '↺',
+                _ if pc == repeated.start => '┌',
+                _ if pc == repeated.end => '└',
+                _ if (repeated.start..repeated.end).contains(&pc) => '│',
+                _ => ' ',
+            })
+            .collect::<String>();
+        let mut notes = Vec::new();
+        let sources = targets
+            .iter()
+            .enumerate()
+            .filter(|(_, target)| **target == Some(Some(pc)))
+            .map(|(source, _)| source.to_string())
+            .collect::<Vec<_>>();
+        if !sources.is_empty() {
+            notes.push(format!("from {}", sources.join(", ")));
+        }
+        match targets[pc] {
+            Some(Some(target)) if target <= pc => notes.push(format!("loops back to {}", target)),
+            Some(Some(target)) => notes.push(format!("jumps to {}", target)),
+            Some(None) => notes.push("jumps out, halting".to_string()),
+            None => {}
+        }
+        let line = format!(
+            "{}{}{:>digits$}  {:<width$}  {}",
+            margin,
+            if margin.is_empty() { "" } else { " " },
+            pc,
+            instruction,
+            notes.join("; "),
+        );
+        writeln!(listing, "{}", line.trim_end()).expect("writing to a string");
+    }
+    listing
+}
+
+/// Values of `register` for which the program outputs exactly `target`, from the lowest.
+///
+/// This reverses programs built around a loop that shifts `bits` bits out of the register in
+/// every round and outputs a value that only depends on the bits still in the register, so the
+/// last output only depends on the highest bits. Starting from the last output, every value that
+/// makes the program print the end of `target` is extended with all combinations of `bits` lower
+/// bits, keeping those that also print the output before. Every run is limited to
+/// `limit` steps, in case a candidate makes the program loop forever.
+pub fn solve_backwards<I>(
+    program: &[I],
+    registers: &[u64],
+    register: usize,
+    bits: u32,
+    target: &[u64],
+    limit: usize,
+) -> Vec<u64>
+where
+    I: Instruction<Value = u64>,
+{
+    let mut candidates = vec![0];
+    for start in (0..target.len()).rev() {
+        candidates = candidates
+            .into_iter()
+            .flat_map(|high: u64| {
+                (0..1 << bits).filter_map(move |low| high.checked_shl(bits).map(|high| high | low))
+            })
+            .filter(|value| {
+                let mut registers = registers.to_vec();
+                registers[register] = *value;
+                let mut machine = Machine::new(program, registers).with_limit(limit);
+                machine.run() == Stop::Halted && machine.output() == &target[start..]
+            })
+            .collect();
+    }
+    candidates.sort_unstable();
+    candidates.dedup();
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A tiny instruction set with a counter in register 0 and an accumulator in register 1.
+    #[derive(Clone, Debug, PartialEq)]
+    enum Toy {
+        Add(usize, i64),
+        Out(usize),
+        JumpIfPositive(usize, isize),
+        Goto(usize),
+        Halt,
+    }
+
+    impl Instruction for Toy {
+        type Value = i64;
+
+        fn execute(&self, registers: &mut [i64], output: &mut Vec<i64>) -> Flow {
+            match *self {
+                Toy::Add(register, value) => registers[register] += value,
+                Toy::Out(register) => output.push(registers[register]),
+                Toy::JumpIfPositive(register, offset) if registers[register] > 0 => {
+                    return Flow::Jump(offset)
+                }
+                Toy::JumpIfPositive(..) => {}
+                Toy::Goto(index) => return Flow::Goto(index),
+                Toy::Halt => return Flow::Halt,
+            }
+            Flow::Next
+        }
+
+        fn branch(&self) -> Option<Flow> {
+            match *self {
+                Toy::JumpIfPositive(_, offset) => Some(Flow::Jump(offset)),
+                Toy::Goto(index) => Some(Flow::Goto(index)),
+                _ => None,
+            }
+        }
+    }
+
+    impl Display for Toy {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Toy::Add(register, value) => write!(f, "add r{} {}", register, value),
+                Toy::Out(register) => write!(f, "out r{}", register),
+                Toy::JumpIfPositive(register, offset) => {
+                    write!(f, "jgz r{} {:+}", register, offset)
+                }
+                Toy::Goto(index) => write!(f, "goto {}", index),
+                Toy::Halt => write!(f, "halt"),
+            }
+        }
+    }
+
+    /// Outputs the lowest bits of register 0 XOR register 1 and shifts them out, in a loop.
+    #[derive(Clone, Debug)]
+    enum Bits {
+        Shift(u32),
+        Out(u64),
+        Loop,
+    }
+
+    impl Instruction for Bits {
+        type Value = u64;
+
+        fn execute(&self, registers: &mut [u64], output: &mut Vec<u64>) -> Flow {
+            match *self {
+                Bits::Shift(bits) => registers[0] >>= bits,
+                Bits::Out(mask) => output.push((registers[0] ^ registers[1]) & mask),
+                Bits::Loop if registers[0] != 0 => return Flow::Goto(0),
+                Bits::Loop => {}
+            }
+            Flow::Next
+        }
+    }
+
+    /// Adds 10 to the accumulator for every count down from 3, printing the counter.
+    const COUNTDOWN: [Toy; 5] = [
+        Toy::Out(0),
+        Toy::Add(1, 10),
+        Toy::Add(0, -1),
+        Toy::JumpIfPositive(0, -3),
+        Toy::Out(1),
+    ];
+
+    #[test]
+    fn runs_until_the_program_ends() {
+        let mut machine = Machine::new(&COUNTDOWN, [3, 0]);
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.registers(), [0, 30]);
+        assert_eq!(machine.output(), [3, 2, 1, 30]);
+        assert_eq!(machine.steps(), 13);
+        assert_eq!(machine.pc(), None);
+        assert!(!machine.step());
+    }
+
+    #[test]
+    fn halts_on_jumps_out_of_the_program() {
+        let program = [Toy::Add(0, 1), Toy::JumpIfPositive(0, -5), Toy::Out(0)];
+        let mut machine = Machine::new(&program, [0]);
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.output(), []);
+
+        let program = [Toy::Goto(2), Toy::Out(0), Toy::Halt, Toy::Out(0)];
+        let mut machine = Machine::new(&program, [0]);
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.steps(), 2);
+        assert_eq!(Machine::new(&[] as &[Toy], [0]).run(), Stop::Halted);
+    }
+
+    #[test]
+    fn stops_at_breakpoints() {
+        let mut machine = Machine::new(&COUNTDOWN, [3, 0]).with_breakpoints([0, 4]);
+        assert_eq!(machine.run(), Stop::Breakpoint(0));
+        assert_eq!(machine.output(), [3]);
+        assert_eq!(machine.run(), Stop::Breakpoint(0));
+        assert_eq!(machine.run(), Stop::Breakpoint(4));
+        assert_eq!(machine.registers(), [0, 30]);
+        machine.registers_mut()[1] = 42;
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.output(), [3, 2, 1, 42]);
+    }
+
+    #[test]
+    fn stops_at_step_limit() {
+        let program = [Toy::Add(0, 1), Toy::Goto(0)];
+        let mut machine = Machine::new(&program, [0]).with_limit(1000);
+        assert_eq!(machine.run(), Stop::Limit);
+        assert_eq!(machine.registers(), [500]);
+        assert_eq!(machine.run(), Stop::Limit);
+    }
+
+    #[test]
+    fn finds_loops() {
+        assert_eq!(loops(&COUNTDOWN), [Loop { start: 0, end: 3 }]);
+        let program = [
+            Toy::Add(0, 1),
+            Toy::JumpIfPositive(0, 0),
+            Toy::Goto(0),
+            Toy::JumpIfPositive(0, 2),
+        ];
+        assert_eq!(
+            loops(&program),
+            [Loop { start: 0, end: 2 }, Loop { start: 1, end: 1 }]
+        );
+    }
+
+    #[test]
+    fn disassembles_programs() {
+        assert_eq!(
+            disassemble(&COUNTDOWN),
+            "\
+┌ 0  out r0     from 3
+│ 1  add r1 10
+│ 2  add r0 -1
+└ 3  jgz r0 -3  loops back to 0
+  4  out r1
+"
+        );
+        let program = [
+            Toy::JumpIfPositive(0, 2),
+            Toy::JumpIfPositive(1, 0),
+            Toy::Goto(7),
+        ];
+        assert_eq!(
+            disassemble(&program),
+            "  0  jgz r0 +2  jumps to 2
+↺ 1  jgz r1 +0  from 1; loops back to 1
+  2  goto 7     from 0; jumps out, halting
+"
+        );
+    }
+
+    #[test]
+    fn solves_backwards() {
+        // Outputs the lowest two bits of A XOR 1 and shifts them out, until A is zero.
+        let program = [Bits::Out(3), Bits::Shift(2), Bits::Loop];
+        let solutions = solve_backwards(&program, &[0, 1], 0, 2, &[0, 3, 2], 1000);
+        assert_eq!(solutions, [0b11_10_01]);
+        let mut machine = Machine::new(&program, [solutions[0], 1]);
+        machine.run();
+        assert_eq!(machine.output(), [0, 3, 2]);
+        assert_eq!(
+            solve_backwards(&program, &[0, 0], 0, 2, &[1, 0], 1000),
+            [] as [u64; 0]
+        );
+    }
+
+    #[test]
+    fn traces_execution() {
+        let mut machine = Machine::new(&COUNTDOWN, [1, 0]).with_trace();
+        assert_eq!(machine.trace(), Some(&[][..]));
+        machine.run();
+        let trace = machine.trace().unwrap();
+        assert_eq!(
+            trace.iter().map(|step| step.pc).collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4]
+        );
+        assert_eq!(trace[1].registers, [1, 10]);
+        assert_eq!(trace[2].instruction, Toy::Add(0, -1));
+        assert_eq!(
+            trace[2].to_string(),
+            "   2  Add(0, -1)                [0, 10]"
+        );
+        assert_eq!(Machine::new(&COUNTDOWN, [1, 0]).trace(), None);
+    }
+}
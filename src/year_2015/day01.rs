@@ -1,5 +1,8 @@
+use crate::ParseError;
 use std::ops::{AddAssign, SubAssign};
 
+crate::solver!(2015, 1, Vec<i32>);
+
 pub fn part1(floors: &[i32]) -> i32 {
     floors.last().cloned().unwrap_or(0)
 }
@@ -8,20 +11,27 @@ pub fn part2(floors: &[i32]) -> usize {
     floors.iter().position(|floor| *floor == -1).unwrap() + 1
 }
 
-pub fn parse(input: &str) -> Vec<i32> {
+pub fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
     input
-        .bytes()
-        .scan(0, |floor, b| {
-            match b {
-                b'(' => {
+        .char_indices()
+        .filter(|(_, c)| !c.is_whitespace())
+        .scan(0, |floor, (index, c)| {
+            match c {
+                '(' => {
                     floor.add_assign(1);
                 }
-                b')' => {
+                ')' => {
                     floor.sub_assign(1);
                 }
-                _ => {}
+                _ => {
+                    return Some(Err(ParseError::at(
+                        input,
+                        &input[index..],
+                        format!("expected '(' or ')', found {:?}", c),
+                    )))
+                }
             };
-            Some(*floor)
+            Some(Ok(*floor))
         })
         .collect()
 }
@@ -42,13 +52,21 @@ mod tests {
     #[case(")))", -3)]
     #[case(")())())", -3)]
     fn solves_part_1_examples(#[case] input: &str, #[case] floor: i32) {
-        assert_eq!(part1(&parse(input)), floor);
+        assert_eq!(part1(&parse(input).unwrap()), floor);
     }
 
     #[rstest]
     #[case(")", 1)]
     #[case("()())", 5)]
     fn solves_part2_examples(#[case] input: &str, #[case] position: usize) {
-        assert_eq!(part2(&parse(input)), position);
+        assert_eq!(part2(&parse(input).unwrap()), position);
+    }
+
+    #[test]
+    fn rejects_other_characters() {
+        assert_eq!(
+            parse("(()\n)x"),
+            Err(ParseError::new(2, 2, "expected '(' or ')', found 'x'"))
+        );
     }
 }
@@ -1,3 +1,8 @@
+use crate::parsing::parse_at;
+use crate::ParseError;
+
+crate::solver!(2015, 2, Vec<Present>);
+
 pub fn part1(presents: &[Present]) -> u32 {
     presents.iter().map(required_paper).sum()
 }
@@ -19,17 +24,25 @@ fn required_rope((l, w, h): &Present) -> u32 {
     2 * edges[0..=1].iter().sum::<u32>() + edges.iter().product::<u32>()
 }
 
-pub fn parse(input: &str) -> Vec<Present> {
+pub fn parse(input: &str) -> Result<Vec<Present>, ParseError> {
     input
         .lines()
         .map(|line| {
-            let split = line
+            let dimensions = line
                 .split('x')
-                .map(|part| part.parse::<u32>().expect("Integer dimension"))
-                .collect::<Vec<_>>();
-            (split[0], split[1], split[2])
+                .map(|part| parse_at::<u32>(input, part, "integer dimension"))
+                .collect::<Result<Vec<_>, _>>()?;
+            if let [l, w, h] = dimensions[..] {
+                Ok((l, w, h))
+            } else {
+                Err(ParseError::at(
+                    input,
+                    line,
+                    format!("expected three dimensions like 2x3x4, found {:?}", line),
+                ))
+            }
         })
-        .collect::<Vec<Present>>()
+        .collect()
 }
 
 #[cfg(test)]
@@ -39,7 +52,20 @@ mod tests {
 
     #[test]
     fn parses_presents() {
-        assert_eq!(vec![(2, 3, 4), (1, 1, 10)], parse("2x3x4\n1x1x10"))
+        assert_eq!(Ok(vec![(2, 3, 4), (1, 1, 10)]), parse("2x3x4\n1x1x10"))
+    }
+
+    #[rstest]
+    #[case(
+        "2x3x4\n1x1",
+        ParseError::new(2, 1, "expected three dimensions like 2x3x4, found \"1x1\"")
+    )]
+    #[case(
+        "2x3x4\n1x1xa",
+        ParseError::new(2, 5, "expected integer dimension, found \"a\"")
+    )]
+    fn rejects_invalid_presents(#[case] input: &str, #[case] error: ParseError) {
+        assert_eq!(parse(input), Err(error));
     }
 
     #[rstest]
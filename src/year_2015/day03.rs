@@ -1,14 +1,22 @@
+use crate::ParseError;
 use std::collections::HashSet;
 
-pub fn parse(input: &str) -> Vec<(i32, i32)> {
+crate::solver!(2015, 3, Vec<(i32, i32)>);
+
+pub fn parse(input: &str) -> Result<Vec<(i32, i32)>, ParseError> {
     input
-        .bytes()
-        .filter_map(|b| match b {
-            b'>' => Some((0, 1)),
-            b'<' => Some((0, -1)),
-            b'^' => Some((1, 0)),
-            b'v' => Some((-1, 0)),
-            _ => None,
+        .char_indices()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(|(index, c)| match c {
+            '>' => Ok((0, 1)),
+            '<' => Ok((0, -1)),
+            '^' => Ok((1, 0)),
+            'v' => Ok((-1, 0)),
+            _ => Err(ParseError::at(
+                input,
+                &input[index..],
+                format!("expected one of '^', 'v', '<' or '>', found {:?}", c),
+            )),
         })
         .collect()
 }
@@ -49,6 +57,6 @@ mod tests {
     #[case("^>v<", 4)]
     #[case("^v^v^v^v^v", 2)]
     fn counts_positions(#[case] input: &str, #[case] count: usize) {
-        assert_eq!(part1(&parse(input)), count);
+        assert_eq!(part1(&parse(input).unwrap()), count);
     }
 }
@@ -0,0 +1,55 @@
+use crate::md5;
+use crate::ParseError;
+
+crate::solver!(2015, 4, String);
+
+pub fn part1(key: &str) -> u64 {
+    mine(key, 5, 1)
+}
+
+pub fn part2(key: &str) -> u64 {
+    mine(key, 6, 1)
+}
+
+/// The lowest number from `start` on whose hash, after the key, starts with `zeros` zeros.
+fn mine(key: &str, zeros: u32, start: u64) -> u64 {
+    md5::find_nonce(key.as_bytes(), start..u64::MAX, |digest| {
+        md5::leading_zeros(digest) >= zeros
+    })
+    .expect("a number with a matching hash")
+}
+
+pub fn parse(input: &str) -> Result<String, ParseError> {
+    match input.trim() {
+        "" => Err(ParseError::end(input, "expected a secret key")),
+        key => Ok(key.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // Mining from 1 takes too long without optimisations, so only the end of the search is run.
+    #[rstest]
+    #[case("abcdef", 609043)]
+    #[case("pqrstuv", 1048970)]
+    fn mines_five_zeros(#[case] key: &str, #[case] number: u64) {
+        assert_eq!(mine(key, 5, number - 20_000), number);
+        assert_eq!(mine(key, 5, number), number);
+    }
+
+    #[test]
+    fn parses_key() {
+        assert_eq!(parse("abcdef\n"), Ok("abcdef".to_string()));
+    }
+
+    #[test]
+    fn rejects_missing_key() {
+        assert_eq!(
+            parse("\n"),
+            Err(ParseError::new(2, 1, "expected a secret key"))
+        );
+    }
+}
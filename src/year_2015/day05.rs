@@ -0,0 +1,94 @@
+use crate::ParseError;
+use itertools::Itertools;
+
+crate::solver!(2015, 5, Vec<String>);
+
+pub fn part1(strings: &[String]) -> usize {
+    strings.iter().filter(|string| is_nice(string)).count()
+}
+
+pub fn part2(strings: &[String]) -> usize {
+    strings.iter().filter(|string| is_nicer(string)).count()
+}
+
+fn is_nice(string: &str) -> bool {
+    let vowels = string.chars().filter(|c| "aeiou".contains(*c)).count();
+    let double = string.chars().tuple_windows().any(|(a, b)| a == b);
+    let forbidden = ["ab", "cd", "pq", "xy"]
+        .iter()
+        .any(|pair| string.contains(pair));
+    vowels >= 3 && double && !forbidden
+}
+
+fn is_nicer(string: &str) -> bool {
+    let bytes = string.as_bytes();
+    // A pair repeats without overlapping if it occurs again after the pair itself.
+    let repeated_pair = (0..bytes.len().saturating_sub(1)).any(|i| {
+        bytes[i + 2..]
+            .windows(2)
+            .any(|pair| pair == &bytes[i..i + 2])
+    });
+    let repeat_with_gap = bytes.windows(3).any(|window| window[0] == window[2]);
+    repeated_pair && repeat_with_gap
+}
+
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    input
+        .lines()
+        .map(|line| match line.find(|c: char| !c.is_ascii_lowercase()) {
+            Some(index) => Err(ParseError::at(
+                input,
+                &line[index..],
+                format!(
+                    "expected a lowercase letter, found {:?}",
+                    &line[index..=index]
+                ),
+            )),
+            None => Ok(line.to_string()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("ugknbfddgicrmopn", true)]
+    #[case("aaa", true)]
+    #[case("jchzalrnumimnmhp", false)]
+    #[case("haegwjzuvuyypxyu", false)]
+    #[case("dvszwmarrgswjxmb", false)]
+    fn solves_part1_examples(#[case] string: &str, #[case] nice: bool) {
+        assert_eq!(is_nice(string), nice);
+    }
+
+    #[rstest]
+    #[case("qjhvhtzxzqqjkmpb", true)]
+    #[case("xxyxx", true)]
+    #[case("aaa", false)]
+    #[case("uurcxstgmygtbstg", false)]
+    #[case("ieodomkazucvgmuy", false)]
+    fn solves_part2_examples(#[case] string: &str, #[case] nice: bool) {
+        assert_eq!(is_nicer(string), nice);
+    }
+
+    #[test]
+    fn counts_nice_strings() {
+        let strings = parse("ugknbfddgicrmopn\naaa\njchzalrnumimnmhp\n").unwrap();
+        assert_eq!(part1(&strings), 2);
+    }
+
+    #[test]
+    fn rejects_other_characters() {
+        assert_eq!(
+            parse("aaa\naBc"),
+            Err(ParseError::new(
+                2,
+                2,
+                "expected a lowercase letter, found \"B\""
+            ))
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use crate::parsing::{parse_at, split_once_at};
+use crate::ParseError;
+
+crate::solver!(2015, 6, Vec<Instruction>);
+
+const SIZE: usize = 1000;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Action {
+    TurnOn,
+    TurnOff,
+    Toggle,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Instruction {
+    action: Action,
+    from: (usize, usize),
+    to: (usize, usize),
+}
+
+pub fn part1(instructions: &[Instruction]) -> u32 {
+    light(instructions, |light, action| match action {
+        Action::TurnOn => 1,
+        Action::TurnOff => 0,
+        Action::Toggle => 1 - light,
+    })
+}
+
+pub fn part2(instructions: &[Instruction]) -> u32 {
+    light(instructions, |light, action| match action {
+        Action::TurnOn => light + 1,
+        Action::TurnOff => light.saturating_sub(1),
+        Action::Toggle => light + 2,
+    })
+}
+
+/// Total brightness after following the instructions, with `update` giving the new brightness.
+fn light(instructions: &[Instruction], update: impl Fn(u32, Action) -> u32) -> u32 {
+    let mut lights = vec![0; SIZE * SIZE];
+    for Instruction { action, from, to } in instructions {
+        for y in from.1..=to.1 {
+            for light in &mut lights[y * SIZE + from.0..=y * SIZE + to.0] {
+                *light = update(*light, *action);
+            }
+        }
+    }
+    lights.iter().sum()
+}
+
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (action, corners) = [
+                ("turn on ", Action::TurnOn),
+                ("turn off ", Action::TurnOff),
+                ("toggle ", Action::Toggle),
+            ]
+            .into_iter()
+            .find_map(|(prefix, action)| Some((action, line.strip_prefix(prefix)?)))
+            .ok_or_else(|| {
+                ParseError::at(
+                    input,
+                    line,
+                    format!(
+                        "expected 'turn on', 'turn off' or 'toggle', found {:?}",
+                        line
+                    ),
+                )
+            })?;
+            let (from, to) = split_once_at(input, corners, " through ")?;
+            let from = parse_corner(input, from)?;
+            let to = parse_corner(input, to)?;
+            if from.0 > to.0 || from.1 > to.1 {
+                return Err(ParseError::at(
+                    input,
+                    corners,
+                    "expected the first corner to be the top left one",
+                ));
+            }
+            Ok(Instruction { action, from, to })
+        })
+        .collect()
+}
+
+fn parse_corner(input: &str, corner: &str) -> Result<(usize, usize), ParseError> {
+    let (x, y) = split_once_at(input, corner, ",")?;
+    let coordinate = |value| {
+        parse_at::<usize>(input, value, "a coordinate").and_then(|coordinate| match coordinate {
+            0..SIZE => Ok(coordinate),
+            _ => Err(ParseError::at(
+                input,
+                value,
+                format!("expected a coordinate below {}, found {}", SIZE, coordinate),
+            )),
+        })
+    };
+    Ok((coordinate(x)?, coordinate(y)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("turn on 0,0 through 999,999", 1_000_000)]
+    #[case("toggle 0,0 through 999,0", 1000)]
+    #[case(
+        "turn on 0,0 through 999,999\nturn off 499,499 through 500,500",
+        999_996
+    )]
+    fn solves_part1_examples(#[case] input: &str, #[case] lit: u32) {
+        assert_eq!(part1(&parse(input).unwrap()), lit);
+    }
+
+    #[rstest]
+    #[case("turn on 0,0 through 0,0", 1)]
+    #[case("toggle 0,0 through 999,999", 2_000_000)]
+    #[case("turn off 0,0 through 0,0\nturn on 0,0 through 0,0", 1)]
+    fn solves_part2_examples(#[case] input: &str, #[case] brightness: u32) {
+        assert_eq!(part2(&parse(input).unwrap()), brightness);
+    }
+
+    #[rstest]
+    #[case(
+        "switch 0,0 through 1,1",
+        ParseError::new(
+            1,
+            1,
+            "expected 'turn on', 'turn off' or 'toggle', found \"switch 0,0 through 1,1\""
+        )
+    )]
+    #[case(
+        "toggle 0,0 through 1000,1",
+        ParseError::new(1, 20, "expected a coordinate below 1000, found 1000")
+    )]
+    #[case(
+        "toggle 5,5 through 1,1",
+        ParseError::new(1, 8, "expected the first corner to be the top left one")
+    )]
+    fn rejects_invalid_instructions(#[case] input: &str, #[case] error: ParseError) {
+        assert_eq!(parse(input), Err(error));
+    }
+}
@@ -0,0 +1,191 @@
+use crate::parsing::{parse_at, split_once_at};
+use crate::ParseError;
+use std::collections::HashMap;
+
+crate::solver!(2015, 7, Circuit);
+
+/// The gate driving each wire, by the name of the wire.
+type Circuit = HashMap<String, Gate>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Signal {
+    Value(u16),
+    Wire(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Gate {
+    Direct(Signal),
+    And(Signal, Signal),
+    Or(Signal, Signal),
+    LeftShift(Signal, u16),
+    RightShift(Signal, u16),
+    Not(Signal),
+}
+
+pub fn part1(circuit: &Circuit) -> u16 {
+    Simulation::new(circuit).signal("a")
+}
+
+pub fn part2(circuit: &Circuit) -> u16 {
+    let a = part1(circuit);
+    let mut circuit = circuit.clone();
+    circuit.insert("b".to_string(), Gate::Direct(Signal::Value(a)));
+    part1(&circuit)
+}
+
+/// Computes the signals on demand, computing each wire once.
+struct Simulation<'a> {
+    circuit: &'a Circuit,
+    signals: HashMap<&'a str, u16>,
+}
+
+impl<'a> Simulation<'a> {
+    fn new(circuit: &'a Circuit) -> Simulation<'a> {
+        Simulation {
+            circuit,
+            signals: HashMap::new(),
+        }
+    }
+
+    fn signal(&mut self, wire: &'a str) -> u16 {
+        if let Some(signal) = self.signals.get(wire) {
+            return *signal;
+        }
+        let gate = self
+            .circuit
+            .get(wire)
+            .unwrap_or_else(|| panic!("wire {} to be connected", wire));
+        let signal = match gate {
+            Gate::Direct(input) => self.input(input),
+            Gate::And(left, right) => self.input(left) & self.input(right),
+            Gate::Or(left, right) => self.input(left) | self.input(right),
+            Gate::LeftShift(input, bits) => self.input(input) << bits,
+            Gate::RightShift(input, bits) => self.input(input) >> bits,
+            Gate::Not(input) => !self.input(input),
+        };
+        self.signals.insert(wire, signal);
+        signal
+    }
+
+    fn input(&mut self, signal: &'a Signal) -> u16 {
+        match signal {
+            Signal::Value(value) => *value,
+            Signal::Wire(wire) => self.signal(wire),
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Circuit, ParseError> {
+    let mut circuit = HashMap::new();
+    for line in input.lines() {
+        let (gate, wire) = split_once_at(input, line, " -> ")?;
+        if !is_wire(wire) {
+            return Err(ParseError::at(
+                input,
+                wire,
+                format!("expected a wire name, found {:?}", wire),
+            ));
+        }
+        let signal = |signal: &str| -> Result<Signal, ParseError> {
+            if is_wire(signal) {
+                Ok(Signal::Wire(signal.to_string()))
+            } else {
+                parse_at(input, signal, "a signal or wire name").map(Signal::Value)
+            }
+        };
+        let shift = |bits| match parse_at::<u16>(input, bits, "a number of bits")? {
+            bits @ 0..16 => Ok(bits),
+            _ => Err(ParseError::at(
+                input,
+                bits,
+                "expected a shift by fewer than 16 bits",
+            )),
+        };
+        let gate = match gate.split(' ').collect::<Vec<_>>()[..] {
+            [value] => Gate::Direct(signal(value)?),
+            ["NOT", value] => Gate::Not(signal(value)?),
+            [left, "AND", right] => Gate::And(signal(left)?, signal(right)?),
+            [left, "OR", right] => Gate::Or(signal(left)?, signal(right)?),
+            [value, "LSHIFT", bits] => Gate::LeftShift(signal(value)?, shift(bits)?),
+            [value, "RSHIFT", bits] => Gate::RightShift(signal(value)?, shift(bits)?),
+            _ => {
+                return Err(ParseError::at(
+                    input,
+                    gate,
+                    format!("expected a signal or gate, found {:?}", gate),
+                ))
+            }
+        };
+        if circuit.insert(wire.to_string(), gate).is_some() {
+            return Err(ParseError::at(
+                input,
+                wire,
+                format!("wire {} is driven twice", wire),
+            ));
+        }
+    }
+    Ok(circuit)
+}
+
+fn is_wire(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "\
+123 -> x
+456 -> y
+x AND y -> d
+x OR y -> e
+x LSHIFT 2 -> f
+y RSHIFT 2 -> g
+NOT x -> h
+NOT y -> i
+";
+
+    #[rstest]
+    #[case("d", 72)]
+    #[case("e", 507)]
+    #[case("f", 492)]
+    #[case("g", 114)]
+    #[case("h", 65412)]
+    #[case("i", 65079)]
+    #[case("x", 123)]
+    #[case("y", 456)]
+    fn simulates_example(#[case] wire: &str, #[case] signal: u16) {
+        let circuit = parse(EXAMPLE).unwrap();
+        assert_eq!(Simulation::new(&circuit).signal(wire), signal);
+    }
+
+    #[test]
+    fn overrides_b_with_a() {
+        let circuit = parse("b -> a\n1 AND c -> b\n3 -> c\n").unwrap();
+        assert_eq!(part1(&circuit), 1);
+        assert_eq!(part2(&circuit), 1);
+        let circuit = parse("b OR c -> a\n1 -> b\n2 -> c\n").unwrap();
+        assert_eq!(part1(&circuit), 3);
+        assert_eq!(part2(&circuit), 3);
+        let circuit = parse("b LSHIFT 1 -> a\n1 -> b\n").unwrap();
+        assert_eq!(part2(&circuit), 4);
+    }
+
+    #[rstest]
+    #[case(
+        "x XOR y -> z",
+        ParseError::new(1, 1, "expected a signal or gate, found \"x XOR y\"")
+    )]
+    #[case(
+        "x LSHIFT 16 -> y",
+        ParseError::new(1, 10, "expected a shift by fewer than 16 bits")
+    )]
+    #[case("1 -> X", ParseError::new(1, 6, "expected a wire name, found \"X\""))]
+    #[case("1 -> x\n2 -> x", ParseError::new(2, 6, "wire x is driven twice"))]
+    fn rejects_invalid_gates(#[case] input: &str, #[case] error: ParseError) {
+        assert_eq!(parse(input), Err(error));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::ParseError;
+
+crate::solver!(2015, 8, Vec<Literal>);
+
+/// Lengths of a string literal, in characters.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Literal {
+    code: usize,
+    memory: usize,
+    encoded: usize,
+}
+
+pub fn part1(literals: &[Literal]) -> usize {
+    literals
+        .iter()
+        .map(|literal| literal.code - literal.memory)
+        .sum()
+}
+
+pub fn part2(literals: &[Literal]) -> usize {
+    literals
+        .iter()
+        .map(|literal| literal.encoded - literal.code)
+        .sum()
+}
+
+pub fn parse(input: &str) -> Result<Vec<Literal>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let content = line
+                .strip_prefix('"')
+                .and_then(|line| line.strip_suffix('"'))
+                .filter(|_| line.len() >= 2)
+                .ok_or_else(|| {
+                    ParseError::at(
+                        input,
+                        line,
+                        format!("expected a quoted string, found {:?}", line),
+                    )
+                })?;
+            Ok(Literal {
+                code: line.len(),
+                memory: memory_length(input, content)?,
+                // Quotes and backslashes are escaped, and the result quoted again.
+                encoded: line.len() + line.matches(['"', '\\']).count() + 2,
+            })
+        })
+        .collect()
+}
+
+/// Number of characters the escaped `content` of a literal stands for.
+fn memory_length(input: &str, content: &str) -> Result<usize, ParseError> {
+    let mut length = 0;
+    let mut rest = content;
+    while let Some(c) = rest.chars().next() {
+        let escape = if c == '\\' { rest.get(..2) } else { None };
+        rest = match escape {
+            Some("\\\\" | "\\\"") => &rest[2..],
+            Some("\\x")
+                if rest
+                    .get(2..4)
+                    .is_some_and(|hex| hex.chars().all(|c| c.is_ascii_hexdigit())) =>
+            {
+                &rest[4..]
+            }
+            _ if c == '\\' || c == '"' => {
+                return Err(ParseError::at(
+                    input,
+                    rest,
+                    format!("expected an escape sequence, found {:?}", rest),
+                ))
+            }
+            _ => &rest[c.len_utf8()..],
+        };
+        length += 1;
+    }
+    Ok(length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = r#"""
+"abc"
+"aaa\"aaa"
+"\x27"
+"#;
+
+    #[rstest]
+    #[case(r#""""#, 2, 0, 6)]
+    #[case(r#""abc""#, 5, 3, 9)]
+    #[case(r#""aaa\"aaa""#, 10, 7, 16)]
+    #[case(r#""\x27""#, 6, 1, 11)]
+    fn measures_literals(
+        #[case] line: &str,
+        #[case] code: usize,
+        #[case] memory: usize,
+        #[case] encoded: usize,
+    ) {
+        assert_eq!(
+            parse(line),
+            Ok(vec![Literal {
+                code,
+                memory,
+                encoded
+            }])
+        );
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 12);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 19);
+    }
+
+    #[rstest]
+    #[case(
+        "abc",
+        ParseError::new(1, 1, "expected a quoted string, found \"abc\"")
+    )]
+    #[case(
+        r#""a\x2""#,
+        ParseError::new(1, 3, "expected an escape sequence, found \"\\\\x2\"")
+    )]
+    #[case(
+        r#""a"b""#,
+        ParseError::new(1, 3, "expected an escape sequence, found \"\\\"b\"")
+    )]
+    fn rejects_invalid_literals(#[case] input: &str, #[case] error: ParseError) {
+        assert_eq!(parse(input), Err(error));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::parsing::{parse_at, split_once_at};
+use crate::ParseError;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+crate::solver!(2015, 9, Distances);
+
+/// Distances between every pair of locations, which are numbered in order of appearance.
+pub struct Distances(Vec<Vec<Option<u32>>>);
+
+pub fn part1(distances: &Distances) -> u32 {
+    distances
+        .routes()
+        .min()
+        .expect("a route through all locations")
+}
+
+pub fn part2(distances: &Distances) -> u32 {
+    distances
+        .routes()
+        .max()
+        .expect("a route through all locations")
+}
+
+impl Distances {
+    /// Lengths of the routes that visit every location once.
+    fn routes(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..self.0.len())
+            .permutations(self.0.len())
+            // Every route is also found in reverse.
+            .filter(|route| route.first() <= route.last())
+            .filter_map(|route| {
+                route
+                    .iter()
+                    .tuple_windows()
+                    .map(|(from, to)| self.0[*from][*to])
+                    .sum::<Option<u32>>()
+            })
+    }
+}
+
+pub fn parse(input: &str) -> Result<Distances, ParseError> {
+    let mut locations = HashMap::new();
+    let mut edges = Vec::new();
+    for line in input.lines() {
+        let (route, distance) = split_once_at(input, line, " = ")?;
+        let (from, to) = split_once_at(input, route, " to ")?;
+        let distance = parse_at::<u32>(input, distance, "a distance")?;
+        let mut index = |location: &str| {
+            let next = locations.len();
+            *locations.entry(location.to_string()).or_insert(next)
+        };
+        edges.push((index(from), index(to), distance));
+    }
+    let mut distances = vec![vec![None; locations.len()]; locations.len()];
+    for (from, to, distance) in edges {
+        distances[from][to] = Some(distance);
+        distances[to][from] = Some(distance);
+    }
+    Ok(Distances(distances))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+London to Dublin = 464
+London to Belfast = 518
+Dublin to Belfast = 141
+";
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 605);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 982);
+    }
+
+    #[test]
+    fn skips_missing_connections() {
+        let distances = parse("A to B = 1\nB to C = 2\nA to D = 5\n").unwrap();
+        assert_eq!(part1(&distances), 8);
+        assert_eq!(part2(&distances), 8);
+    }
+
+    #[test]
+    fn rejects_invalid_distances() {
+        assert_eq!(
+            parse("A to B = far").err(),
+            Some(ParseError::new(1, 10, "expected a distance, found \"far\""))
+        );
+    }
+}
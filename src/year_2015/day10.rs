@@ -0,0 +1,66 @@
+use crate::ParseError;
+use itertools::{iterate, Itertools};
+
+crate::solver!(2015, 10, Vec<u8>);
+
+pub fn part1(digits: &[u8]) -> usize {
+    iterate(digits.to_vec(), |digits| look_and_say(digits))
+        .nth(40)
+        .expect("a sequence after 40 rounds")
+        .len()
+}
+
+pub fn part2(digits: &[u8]) -> usize {
+    iterate(digits.to_vec(), |digits| look_and_say(digits))
+        .nth(50)
+        .expect("a sequence after 50 rounds")
+        .len()
+}
+
+fn look_and_say(digits: &[u8]) -> Vec<u8> {
+    let mut said = Vec::with_capacity(digits.len() * 2);
+    for (count, digit) in digits.iter().dedup_with_count() {
+        said.extend([count as u8, *digit]);
+    }
+    said
+}
+
+pub fn parse(input: &str) -> Result<Vec<u8>, ParseError> {
+    input
+        .trim_end()
+        .char_indices()
+        .map(|(index, c)| {
+            c.to_digit(10).map(|digit| digit as u8).ok_or_else(|| {
+                ParseError::at(
+                    input,
+                    &input[index..],
+                    format!("expected a digit, found {:?}", c),
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1", "11")]
+    #[case("11", "21")]
+    #[case("21", "1211")]
+    #[case("1211", "111221")]
+    #[case("111221", "312211")]
+    fn looks_and_says(#[case] digits: &str, #[case] said: &str) {
+        assert_eq!(look_and_say(&parse(digits).unwrap()), parse(said).unwrap());
+    }
+
+    #[test]
+    fn rejects_other_characters() {
+        assert_eq!(
+            parse("12a\n"),
+            Err(ParseError::new(1, 3, "expected a digit, found 'a'"))
+        );
+    }
+}
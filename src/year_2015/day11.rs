@@ -0,0 +1,106 @@
+use crate::ParseError;
+use itertools::Itertools;
+
+crate::solver!(2015, 11, Vec<u8>);
+
+pub fn part1(password: &[u8]) -> String {
+    String::from_utf8(next_valid(password)).expect("lowercase letters")
+}
+
+pub fn part2(password: &[u8]) -> String {
+    String::from_utf8(next_valid(&next_valid(password))).expect("lowercase letters")
+}
+
+fn next_valid(password: &[u8]) -> Vec<u8> {
+    let mut password = password.to_vec();
+    increment(&mut password);
+    while !is_valid(&password) {
+        increment(&mut password);
+    }
+    password
+}
+
+/// Counts up to the next password without confusing letters.
+fn increment(password: &mut [u8]) {
+    // Passwords containing a confusing letter are all skipped at once.
+    if let Some(index) = password.iter().position(|c| is_confusing(*c)) {
+        password[index] += 1;
+        password[index + 1..].fill(b'a');
+        return;
+    }
+    for c in password.iter_mut().rev() {
+        if *c == b'z' {
+            *c = b'a';
+        } else {
+            *c += if is_confusing(*c + 1) { 2 } else { 1 };
+            return;
+        }
+    }
+}
+
+fn is_valid(password: &[u8]) -> bool {
+    let straight = password
+        .iter()
+        .tuple_windows()
+        .any(|(a, b, c)| a + 1 == *b && b + 1 == *c);
+    let pairs = password
+        .iter()
+        .tuple_windows()
+        .filter(|(a, b)| a == b)
+        .map(|(a, _)| a)
+        .unique()
+        .count();
+    straight && pairs >= 2 && !password.iter().any(|c| is_confusing(*c))
+}
+
+fn is_confusing(c: u8) -> bool {
+    matches!(c, b'i' | b'o' | b'l')
+}
+
+pub fn parse(input: &str) -> Result<Vec<u8>, ParseError> {
+    let password = input.trim_end();
+    match password.find(|c: char| !c.is_ascii_lowercase()) {
+        Some(index) => Err(ParseError::at(
+            input,
+            &password[index..],
+            "expected a lowercase letter",
+        )),
+        None if password.is_empty() => Err(ParseError::end(input, "expected a password")),
+        None => Ok(password.as_bytes().to_vec()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("hijklmmn", false)]
+    #[case("abbceffg", false)]
+    #[case("abbcegjk", false)]
+    #[case("abcdffaa", true)]
+    #[case("ghjaabcc", true)]
+    fn validates_passwords(#[case] password: &str, #[case] valid: bool) {
+        assert_eq!(is_valid(password.as_bytes()), valid);
+    }
+
+    #[rstest]
+    #[case("xx", "xy")]
+    #[case("xz", "ya")]
+    #[case("ah", "aj")]
+    #[case("azz", "baa")]
+    #[case("aiz", "aja")]
+    fn increments_passwords(#[case] password: &str, #[case] next: &str) {
+        let mut password = password.as_bytes().to_vec();
+        increment(&mut password);
+        assert_eq!(password, next.as_bytes());
+    }
+
+    #[rstest]
+    #[case("abcdefgh", "abcdffaa")]
+    #[case("ghijklmn", "ghjaabcc")]
+    fn solves_part1_examples(#[case] password: &str, #[case] next: &str) {
+        assert_eq!(part1(&parse(password).unwrap()), next);
+    }
+}
@@ -0,0 +1,234 @@
+use crate::parsing::parse_at;
+use crate::ParseError;
+
+crate::solver!(2015, 12, Json);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(i64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+pub fn part1(document: &Json) -> i64 {
+    sum(document, &|_| false)
+}
+
+pub fn part2(document: &Json) -> i64 {
+    sum(document, &|members| {
+        members
+            .iter()
+            .any(|(_, value)| *value == Json::String("red".to_string()))
+    })
+}
+
+/// Sum of all numbers, except in the objects that are `ignored`.
+fn sum(value: &Json, ignored: &impl Fn(&[(String, Json)]) -> bool) -> i64 {
+    match value {
+        Json::Number(number) => *number,
+        Json::Array(values) => values.iter().map(|value| sum(value, ignored)).sum(),
+        Json::Object(members) if !ignored(members) => {
+            members.iter().map(|(_, value)| sum(value, ignored)).sum()
+        }
+        _ => 0,
+    }
+}
+
+pub fn parse(input: &str) -> Result<Json, ParseError> {
+    let mut parser = Parser { input, rest: input };
+    let document = parser.value()?;
+    parser.skip_whitespace();
+    if parser.rest.is_empty() {
+        Ok(document)
+    } else {
+        Err(parser.error("the end of the document"))
+    }
+}
+
+/// Reads JSON values from the front of `rest`, the unread part of `input`.
+struct Parser<'a> {
+    input: &'a str,
+    rest: &'a str,
+}
+
+impl Parser<'_> {
+    fn value(&mut self) -> Result<Json, ParseError> {
+        self.skip_whitespace();
+        match self.rest.chars().next() {
+            Some('[') => {
+                let values = self.sequence('[', ']', Parser::value)?;
+                Ok(Json::Array(values))
+            }
+            Some('{') => {
+                let members = self.sequence('{', '}', |parser| {
+                    parser.skip_whitespace();
+                    let key = parser.string()?;
+                    parser.skip_whitespace();
+                    parser.expect(':')?;
+                    Ok((key, parser.value()?))
+                })?;
+                Ok(Json::Object(members))
+            }
+            Some('"') => Ok(Json::String(self.string()?)),
+            Some('-' | '0'..='9') => {
+                let end = self.rest[1..]
+                    .find(|c: char| !c.is_ascii_digit())
+                    .map_or(self.rest.len(), |end| end + 1);
+                let number = parse_at(self.input, &self.rest[..end], "a whole number")?;
+                self.rest = &self.rest[end..];
+                Ok(Json::Number(number))
+            }
+            _ => {
+                for (literal, value) in [
+                    ("true", Json::Bool(true)),
+                    ("false", Json::Bool(false)),
+                    ("null", Json::Null),
+                ] {
+                    if let Some(rest) = self.rest.strip_prefix(literal) {
+                        self.rest = rest;
+                        return Ok(value);
+                    }
+                }
+                Err(self.error("a value"))
+            }
+        }
+    }
+
+    /// Values separated by commas between `open` and `close`.
+    fn sequence<T>(
+        &mut self,
+        open: char,
+        close: char,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.expect(open)?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if let Some(rest) = self.rest.strip_prefix(close) {
+            self.rest = rest;
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            self.skip_whitespace();
+            match self.rest.chars().next() {
+                Some(',') => self.rest = &self.rest[1..],
+                Some(c) if c == close => {
+                    self.rest = &self.rest[1..];
+                    return Ok(items);
+                }
+                _ => return Err(self.error(&format!("',' or '{}'", close))),
+            }
+        }
+    }
+
+    /// A string without escape sequences, which the puzzle does not use.
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.expect('"')?;
+        match self.rest.find(['"', '\\']) {
+            Some(end) if self.rest[end..].starts_with('"') => {
+                let string = self.rest[..end].to_string();
+                self.rest = &self.rest[end + 1..];
+                Ok(string)
+            }
+            Some(end) => {
+                self.rest = &self.rest[end..];
+                Err(self.error("a string without escape sequences"))
+            }
+            None => Err(ParseError::end(self.input, "expected '\"'")),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        match self.rest.strip_prefix(expected) {
+            Some(rest) => {
+                self.rest = rest;
+                Ok(())
+            }
+            None => Err(self.error(&format!("{:?}", expected))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    fn error(&self, expected: &str) -> ParseError {
+        match self.rest.chars().next() {
+            Some(found) => ParseError::at(
+                self.input,
+                self.rest,
+                format!("expected {}, found {:?}", expected, found),
+            ),
+            None => ParseError::end(self.input, format!("expected {}", expected)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("[1,2,3]", 6)]
+    #[case(r#"{"a":2,"b":4}"#, 6)]
+    #[case("[[[3]]]", 3)]
+    #[case(r#"{"a":{"b":4},"c":-1}"#, 3)]
+    #[case(r#"{"a":[-1,1]}"#, 0)]
+    #[case(r#"[-1,{"a":1}]"#, 0)]
+    #[case("[]", 0)]
+    #[case("{}", 0)]
+    fn solves_part1_examples(#[case] input: &str, #[case] total: i64) {
+        assert_eq!(part1(&parse(input).unwrap()), total);
+    }
+
+    #[rstest]
+    #[case("[1,2,3]", 6)]
+    #[case(r#"[1,{"c":"red","b":2},3]"#, 4)]
+    #[case(r#"{"d":"red","e":[1,2,3,4],"f":5}"#, 0)]
+    #[case(r#"[1,"red",5]"#, 6)]
+    fn solves_part2_examples(#[case] input: &str, #[case] total: i64) {
+        assert_eq!(part2(&parse(input).unwrap()), total);
+    }
+
+    #[test]
+    fn parses_documents() {
+        assert_eq!(
+            parse(" { \"a\" : [ true, null, \"x\" ], \"b\": -12 }\n"),
+            Ok(Json::Object(vec![
+                (
+                    "a".to_string(),
+                    Json::Array(vec![
+                        Json::Bool(true),
+                        Json::Null,
+                        Json::String("x".to_string())
+                    ])
+                ),
+                ("b".to_string(), Json::Number(-12)),
+            ]))
+        );
+    }
+
+    #[rstest]
+    #[case("[1,2", ParseError::new(1, 5, "expected ',' or ']'"))]
+    #[case("[1;2]", ParseError::new(1, 3, "expected ',' or ']', found ';'"))]
+    #[case("{\"a\" 1}", ParseError::new(1, 6, "expected ':', found '1'"))]
+    #[case("{1:2}", ParseError::new(1, 2, "expected '\"', found '1'"))]
+    #[case(
+        "[\"a\\\"\"]",
+        ParseError::new(1, 4, "expected a string without escape sequences, found '\\\\'")
+    )]
+    #[case("[-]", ParseError::new(1, 2, "expected a whole number, found \"-\""))]
+    #[case("[x]", ParseError::new(1, 2, "expected a value, found 'x'"))]
+    #[case(
+        "[] []",
+        ParseError::new(1, 4, "expected the end of the document, found '['")
+    )]
+    fn rejects_invalid_documents(#[case] input: &str, #[case] error: ParseError) {
+        assert_eq!(parse(input), Err(error));
+    }
+}
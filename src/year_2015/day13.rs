@@ -0,0 +1,123 @@
+use crate::parsing::parse_at;
+use crate::ParseError;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+crate::solver!(2015, 13, Happiness);
+
+/// Change in happiness of a guest sitting next to another, guests numbered in order of appearance.
+pub struct Happiness(Vec<Vec<i32>>);
+
+pub fn part1(happiness: &Happiness) -> i32 {
+    happiness.best_arrangement()
+}
+
+pub fn part2(happiness: &Happiness) -> i32 {
+    // You are indifferent to everyone, and they to you.
+    let mut with_you = happiness.0.clone();
+    for row in &mut with_you {
+        row.push(0);
+    }
+    with_you.push(vec![0; with_you.len() + 1]);
+    Happiness(with_you).best_arrangement()
+}
+
+impl Happiness {
+    fn best_arrangement(&self) -> i32 {
+        let guests = self.0.len();
+        // The table is round, so the first guest can always take the same seat.
+        (1..guests)
+            .permutations(guests - 1)
+            .map(|others| {
+                let seating = [0].into_iter().chain(others).collect::<Vec<_>>();
+                seating
+                    .iter()
+                    .circular_tuple_windows()
+                    .map(|(a, b)| self.0[*a][*b] + self.0[*b][*a])
+                    .sum()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Happiness, ParseError> {
+    let mut guests = HashMap::new();
+    let mut changes = Vec::new();
+    for line in input.lines() {
+        let words = line.trim_end_matches('.').split(' ').collect::<Vec<_>>();
+        let [guest, "would", direction, units, "happiness", "units", "by", "sitting", "next", "to", neighbour] =
+            words[..]
+        else {
+            return Err(ParseError::at(
+                input,
+                line,
+                "expected '<guest> would gain|lose <units> happiness units by sitting next to <guest>.'",
+            ));
+        };
+        let units = parse_at::<i32>(input, units, "happiness units")?;
+        let change = match direction {
+            "gain" => units,
+            "lose" => -units,
+            _ => {
+                return Err(ParseError::at(
+                    input,
+                    direction,
+                    format!("expected 'gain' or 'lose', found {:?}", direction),
+                ))
+            }
+        };
+        let mut index = |guest: &str| {
+            let next = guests.len();
+            *guests.entry(guest.to_string()).or_insert(next)
+        };
+        changes.push((index(guest), index(neighbour), change));
+    }
+    let mut happiness = vec![vec![0; guests.len()]; guests.len()];
+    for (guest, neighbour, change) in changes {
+        happiness[guest][neighbour] = change;
+    }
+    Ok(Happiness(happiness))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Alice would gain 54 happiness units by sitting next to Bob.
+Alice would lose 79 happiness units by sitting next to Carol.
+Alice would lose 2 happiness units by sitting next to David.
+Bob would gain 83 happiness units by sitting next to Alice.
+Bob would lose 7 happiness units by sitting next to Carol.
+Bob would lose 63 happiness units by sitting next to David.
+Carol would lose 62 happiness units by sitting next to Alice.
+Carol would gain 60 happiness units by sitting next to Bob.
+Carol would gain 55 happiness units by sitting next to David.
+David would gain 46 happiness units by sitting next to Alice.
+David would lose 7 happiness units by sitting next to Bob.
+David would gain 41 happiness units by sitting next to Carol.
+";
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 330);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 286);
+    }
+
+    #[test]
+    fn rejects_invalid_lines() {
+        assert_eq!(
+            parse("Alice would win 54 happiness units by sitting next to Bob.").err(),
+            Some(ParseError::new(
+                1,
+                13,
+                "expected 'gain' or 'lose', found \"win\""
+            ))
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use crate::parsing::parse_at;
+use crate::ParseError;
+
+crate::solver!(2015, 14, Vec<Reindeer>);
+
+const RACE_SECONDS: u32 = 2503;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Reindeer {
+    speed: u32,
+    flying: u32,
+    resting: u32,
+}
+
+impl Reindeer {
+    fn distance(&self, seconds: u32) -> u32 {
+        let period = self.flying + self.resting;
+        let flown = seconds / period * self.flying + (seconds % period).min(self.flying);
+        flown * self.speed
+    }
+}
+
+pub fn part1(reindeer: &[Reindeer]) -> u32 {
+    winning_distance(reindeer, RACE_SECONDS)
+}
+
+pub fn part2(reindeer: &[Reindeer]) -> u32 {
+    winning_points(reindeer, RACE_SECONDS)
+}
+
+fn winning_distance(reindeer: &[Reindeer], seconds: u32) -> u32 {
+    reindeer
+        .iter()
+        .map(|reindeer| reindeer.distance(seconds))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Most points of a reindeer, which gets a point for every second it is in the lead.
+fn winning_points(reindeer: &[Reindeer], seconds: u32) -> u32 {
+    let mut points = vec![0; reindeer.len()];
+    for second in 1..=seconds {
+        let lead = winning_distance(reindeer, second);
+        for (points, reindeer) in points.iter_mut().zip(reindeer) {
+            if reindeer.distance(second) == lead {
+                *points += 1;
+            }
+        }
+    }
+    points.into_iter().max().unwrap_or(0)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Reindeer>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let words = line.split(' ').collect::<Vec<_>>();
+            let [_, "can", "fly", speed, "km/s", "for", flying, "seconds,", "but", "then", "must", "rest", "for", resting, "seconds."] =
+                words[..]
+            else {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    "expected '<name> can fly <speed> km/s for <seconds> seconds, \
+                     but then must rest for <seconds> seconds.'",
+                ));
+            };
+            let number = |value| parse_at(input, value, "a whole number");
+            let reindeer = Reindeer {
+                speed: number(speed)?,
+                flying: number(flying)?,
+                resting: number(resting)?,
+            };
+            if reindeer.flying + reindeer.resting == 0 {
+                return Err(ParseError::at(input, flying, "expected a positive time"));
+            }
+            Ok(reindeer)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "\
+Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.
+Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.
+";
+
+    #[rstest]
+    #[case(1, [14, 16])]
+    #[case(10, [140, 160])]
+    #[case(11, [140, 176])]
+    #[case(1000, [1120, 1056])]
+    fn flies(#[case] seconds: u32, #[case] distances: [u32; 2]) {
+        let reindeer = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            reindeer
+                .iter()
+                .map(|reindeer| reindeer.distance(seconds))
+                .collect::<Vec<_>>(),
+            distances
+        );
+    }
+
+    #[test]
+    fn solves_part1_example() {
+        assert_eq!(winning_distance(&parse(EXAMPLE).unwrap(), 1000), 1120);
+    }
+
+    #[test]
+    fn solves_part2_example() {
+        assert_eq!(winning_points(&parse(EXAMPLE).unwrap(), 1000), 689);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::parsing::{parse_at, split_once_at};
+use crate::ParseError;
+
+crate::solver!(2015, 15, Vec<Ingredient>);
+
+const TEASPOONS: i64 = 100;
+const CALORIES: i64 = 500;
+const PROPERTIES: [&str; 5] = ["capacity", "durability", "flavor", "texture", "calories"];
+
+/// Properties per teaspoon, in the order of [`PROPERTIES`].
+type Ingredient = [i64; 5];
+
+pub fn part1(ingredients: &[Ingredient]) -> i64 {
+    best_score(ingredients, |_| true)
+}
+
+pub fn part2(ingredients: &[Ingredient]) -> i64 {
+    best_score(ingredients, |calories| calories == CALORIES)
+}
+
+/// Highest score of a cookie whose calories are `allowed`.
+fn best_score(ingredients: &[Ingredient], allowed: impl Fn(i64) -> bool) -> i64 {
+    let mut best = 0;
+    for_each_recipe(
+        ingredients.len(),
+        TEASPOONS,
+        &mut Vec::new(),
+        &mut |amounts| {
+            let mut totals = [0; 5];
+            for (ingredient, amount) in ingredients.iter().zip(amounts) {
+                for (total, property) in totals.iter_mut().zip(ingredient) {
+                    *total += property * amount;
+                }
+            }
+            if allowed(totals[4]) {
+                best = best.max(totals[..4].iter().map(|total| total.max(&0)).product());
+            }
+        },
+    );
+    best
+}
+
+/// Calls `visit` with every way to divide `teaspoons` over `count` ingredients.
+fn for_each_recipe(
+    count: usize,
+    teaspoons: i64,
+    amounts: &mut Vec<i64>,
+    visit: &mut impl FnMut(&[i64]),
+) {
+    if amounts.len() + 1 >= count {
+        amounts.push(teaspoons);
+        visit(amounts);
+        amounts.pop();
+        return;
+    }
+    for amount in 0..=teaspoons {
+        amounts.push(amount);
+        for_each_recipe(count, teaspoons - amount, amounts, visit);
+        amounts.pop();
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Ingredient>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (_, properties) = split_once_at(input, line, ": ")?;
+            let mut ingredient = [0; 5];
+            let values = properties.split(", ").collect::<Vec<_>>();
+            if values.len() != PROPERTIES.len() {
+                return Err(ParseError::at(
+                    input,
+                    properties,
+                    format!("expected the properties {}", PROPERTIES.join(", ")),
+                ));
+            }
+            for ((value, name), property) in values.into_iter().zip(PROPERTIES).zip(&mut ingredient)
+            {
+                let (found, amount) = split_once_at(input, value, " ")?;
+                if found != name {
+                    return Err(ParseError::at(
+                        input,
+                        found,
+                        format!("expected {}, found {:?}", name, found),
+                    ));
+                }
+                *property = parse_at(input, amount, "an amount")?;
+            }
+            Ok(ingredient)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8
+Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3
+";
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 62842880);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 57600000);
+    }
+
+    #[test]
+    fn rejects_missing_properties() {
+        assert_eq!(
+            parse("Sugar: capacity 3, flavor 2, durability 0, texture 0, calories 1").err(),
+            Some(ParseError::new(
+                1,
+                20,
+                "expected durability, found \"flavor\""
+            ))
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use crate::parsing::{parse_at, split_once_at};
+use crate::ParseError;
+use std::collections::HashMap;
+
+crate::solver!(2015, 16, Vec<Aunt>);
+
+/// What the MFCSAM detected on the gift.
+const TICKER_TAPE: [(&str, u32); 10] = [
+    ("children", 3),
+    ("cats", 7),
+    ("samoyeds", 2),
+    ("pomeranians", 3),
+    ("akitas", 0),
+    ("vizslas", 0),
+    ("goldfish", 5),
+    ("trees", 3),
+    ("cars", 2),
+    ("perfumes", 1),
+];
+
+/// The number of an aunt Sue and the things remembered about her.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Aunt {
+    number: u32,
+    things: HashMap<String, u32>,
+}
+
+pub fn part1(aunts: &[Aunt]) -> u32 {
+    find_aunt(aunts, |_, count, detected| count == detected)
+}
+
+pub fn part2(aunts: &[Aunt]) -> u32 {
+    // Cat and tree readings are lower bounds, pomeranian and goldfish readings upper bounds.
+    find_aunt(aunts, |thing, count, detected| match thing {
+        "cats" | "trees" => count > detected,
+        "pomeranians" | "goldfish" => count < detected,
+        _ => count == detected,
+    })
+}
+
+/// The number of the only aunt whose remembered things all agree with the ticker tape.
+fn find_aunt(aunts: &[Aunt], agrees: impl Fn(&str, u32, u32) -> bool) -> u32 {
+    let mut matching = aunts.iter().filter(|aunt| {
+        TICKER_TAPE.iter().all(|(thing, detected)| {
+            aunt.things
+                .get(*thing)
+                .is_none_or(|count| agrees(thing, *count, *detected))
+        })
+    });
+    let aunt = matching.next().expect("an aunt that sent the gift");
+    assert!(matching.next().is_none(), "only one aunt to match");
+    aunt.number
+}
+
+pub fn parse(input: &str) -> Result<Vec<Aunt>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (name, things) = split_once_at(input, line, ": ")?;
+            let number = name.strip_prefix("Sue ").ok_or_else(|| {
+                ParseError::at(
+                    input,
+                    name,
+                    format!("expected 'Sue <number>', found {:?}", name),
+                )
+            })?;
+            let things = things
+                .split(", ")
+                .map(|thing| {
+                    let (thing, count) = split_once_at(input, thing, ": ")?;
+                    if !TICKER_TAPE.iter().any(|(known, _)| *known == thing) {
+                        return Err(ParseError::at(
+                            input,
+                            thing,
+                            format!("expected a compound the MFCSAM detects, found {:?}", thing),
+                        ));
+                    }
+                    Ok((thing.to_string(), parse_at(input, count, "a count")?))
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(Aunt {
+                number: parse_at(input, number, "the number of an aunt")?,
+                things,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUNTS: &str = "\
+Sue 1: goldfish: 6, trees: 9, akitas: 0
+Sue 2: children: 3, cats: 7, perfumes: 1
+Sue 3: cats: 8, pomeranians: 2, vizslas: 0
+Sue 4: goldfish: 5, samoyeds: 3, cars: 2
+";
+
+    #[test]
+    fn finds_exact_match() {
+        assert_eq!(part1(&parse(AUNTS).unwrap()), 2);
+    }
+
+    #[test]
+    fn finds_match_with_ranges() {
+        assert_eq!(part2(&parse(AUNTS).unwrap()), 3);
+    }
+
+    #[test]
+    fn rejects_unknown_compounds() {
+        assert_eq!(
+            parse("Sue 1: dogs: 2").err(),
+            Some(ParseError::new(
+                1,
+                8,
+                "expected a compound the MFCSAM detects, found \"dogs\""
+            ))
+        );
+    }
+}
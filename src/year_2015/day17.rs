@@ -0,0 +1,66 @@
+use crate::parsing::parse_at;
+use crate::ParseError;
+
+crate::solver!(2015, 17, Vec<usize>);
+
+const EGGNOG: usize = 150;
+
+pub fn part1(containers: &[usize]) -> usize {
+    combinations(containers, EGGNOG).iter().sum()
+}
+
+pub fn part2(containers: &[usize]) -> usize {
+    combinations(containers, EGGNOG)
+        .into_iter()
+        .find(|ways| *ways > 0)
+        .unwrap_or(0)
+}
+
+/// Ways to fill exactly `liters` by the number of containers used.
+fn combinations(containers: &[usize], liters: usize) -> Vec<usize> {
+    // ways[volume][count] is the number of ways to hold `volume` in `count` containers.
+    let mut ways = vec![vec![0; containers.len() + 1]; liters + 1];
+    ways[0][0] = 1;
+    for (used, container) in containers.iter().enumerate() {
+        for volume in (*container..=liters).rev() {
+            for count in (1..=used + 1).rev() {
+                ways[volume][count] += ways[volume - container][count - 1];
+            }
+        }
+    }
+    ways.swap_remove(liters)
+}
+
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    input
+        .split_whitespace()
+        .map(|container| parse_at(input, container, "a container size"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "20\n15\n10\n5\n5\n";
+
+    #[test]
+    fn counts_combinations() {
+        assert_eq!(
+            combinations(&parse(EXAMPLE).unwrap(), 25),
+            vec![0, 0, 3, 1, 0, 0]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_sizes() {
+        assert_eq!(
+            parse("20\n-5\n"),
+            Err(ParseError::new(
+                2,
+                1,
+                "expected a container size, found \"-5\""
+            ))
+        );
+    }
+}
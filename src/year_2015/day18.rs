@@ -0,0 +1,102 @@
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::ParseError;
+use itertools::iterate;
+
+crate::solver!(2015, 18, Grid<bool>);
+
+const STEPS: usize = 100;
+
+pub fn part1(lights: &Grid<bool>) -> usize {
+    count_on(&animate(lights, STEPS, false))
+}
+
+pub fn part2(lights: &Grid<bool>) -> usize {
+    count_on(&animate(lights, STEPS, true))
+}
+
+fn animate(lights: &Grid<bool>, steps: usize, stuck_corners: bool) -> Grid<bool> {
+    let stick = |mut lights: Grid<bool>| {
+        if stuck_corners {
+            for corner in corners(&lights) {
+                lights[corner] = true;
+            }
+        }
+        lights
+    };
+    iterate(stick(lights.clone()), |lights| stick(step(lights)))
+        .nth(steps)
+        .expect("lights after every step")
+}
+
+/// The next state of the lights according to the rules of the game of life.
+fn step(lights: &Grid<bool>) -> Grid<bool> {
+    let mut next = lights.clone();
+    for (point, on) in lights.iter() {
+        let neighbours = point
+            .neighbours8()
+            .filter(|neighbour| lights.get(*neighbour) == Some(&true))
+            .count();
+        next[point] = matches!((on, neighbours), (true, 2 | 3) | (false, 3));
+    }
+    next
+}
+
+fn corners(lights: &Grid<bool>) -> [Point; 4] {
+    let (bottom, right) = (lights.height() as isize - 1, lights.width() as isize - 1);
+    [
+        Point::new(0, 0),
+        Point::new(0, right),
+        Point::new(bottom, 0),
+        Point::new(bottom, right),
+    ]
+}
+
+fn count_on(lights: &Grid<bool>) -> usize {
+    lights.iter().filter(|(_, on)| **on).count()
+}
+
+pub fn parse(input: &str) -> Result<Grid<bool>, ParseError> {
+    Grid::parse(input, "'#' or '.'", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "\
+.#.#.#
+...##.
+#....#
+..#...
+#.#..#
+####..
+";
+
+    #[rstest]
+    #[case(1, 11)]
+    #[case(2, 8)]
+    #[case(4, 4)]
+    fn animates(#[case] steps: usize, #[case] on: usize) {
+        assert_eq!(
+            count_on(&animate(&parse(EXAMPLE).unwrap(), steps, false)),
+            on
+        );
+    }
+
+    #[rstest]
+    #[case(0, 17)]
+    #[case(1, 18)]
+    #[case(5, 17)]
+    fn animates_with_stuck_corners(#[case] steps: usize, #[case] on: usize) {
+        assert_eq!(
+            count_on(&animate(&parse(EXAMPLE).unwrap(), steps, true)),
+            on
+        );
+    }
+}
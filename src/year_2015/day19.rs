@@ -0,0 +1,130 @@
+use crate::parsing::split_once_at;
+use crate::ParseError;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+crate::solver!(2015, 19, Input);
+
+pub struct Input {
+    replacements: Vec<(String, String)>,
+    molecule: String,
+}
+
+pub fn part1(input: &Input) -> usize {
+    let mut molecules = HashSet::new();
+    for (from, to) in &input.replacements {
+        for (index, _) in input.molecule.match_indices(from.as_str()) {
+            molecules.insert(replace(&input.molecule, index, from, to));
+        }
+    }
+    molecules.len()
+}
+
+pub fn part2(input: &Input) -> usize {
+    reduce(&input.molecule, &input.replacements, &mut HashSet::new())
+        .expect("a way to fabricate the molecule")
+}
+
+/// Number of steps to fabricate `molecule` from a single electron, found by undoing replacements.
+///
+/// The replacements of the puzzle add the same number of steps whichever way a molecule is
+/// fabricated, so the first way found is also the shortest one. Undoing the rightmost
+/// replacement first, like a parser reducing its input, rarely needs to backtrack.
+fn reduce(
+    molecule: &str,
+    replacements: &[(String, String)],
+    dead_ends: &mut HashSet<String>,
+) -> Option<usize> {
+    if dead_ends.contains(molecule) {
+        return None;
+    }
+    let mut candidates = Vec::new();
+    for (from, to) in replacements {
+        // An electron can only be replaced at the very start.
+        if from == "e" {
+            if molecule == to {
+                return Some(1);
+            }
+            continue;
+        }
+        for (index, _) in molecule.match_indices(to.as_str()) {
+            candidates.push((
+                Reverse(index + to.len()),
+                Reverse(to.len()),
+                index,
+                from,
+                to,
+            ));
+        }
+    }
+    candidates.sort_unstable();
+    for (_, _, index, from, to) in candidates {
+        let reduced = replace(molecule, index, to, from);
+        if let Some(steps) = reduce(&reduced, replacements, dead_ends) {
+            return Some(steps + 1);
+        }
+    }
+    dead_ends.insert(molecule.to_string());
+    None
+}
+
+/// `molecule` with the occurrence of `from` at `index` replaced by `to`.
+fn replace(molecule: &str, index: usize, from: &str, to: &str) -> String {
+    [&molecule[..index], to, &molecule[index + from.len()..]].concat()
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let (replacements, molecule) = split_once_at(input, input, "\n\n")?;
+    let replacements = replacements
+        .lines()
+        .map(|line| {
+            let (from, to) = split_once_at(input, line, " => ")?;
+            Ok((from.to_string(), to.to_string()))
+        })
+        .collect::<Result<_, ParseError>>()?;
+    match molecule.trim() {
+        "" => Err(ParseError::end(input, "expected the medicine molecule")),
+        molecule => Ok(Input {
+            replacements,
+            molecule: molecule.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const REPLACEMENTS: &str = "\
+e => H
+e => O
+H => HO
+H => OH
+O => HH
+";
+
+    #[rstest]
+    #[case("HOH", 4)]
+    #[case("HOHOHO", 7)]
+    fn solves_part1_examples(#[case] molecule: &str, #[case] molecules: usize) {
+        let input = parse(&format!("{}\n{}\n", REPLACEMENTS, molecule)).unwrap();
+        assert_eq!(part1(&input), molecules);
+    }
+
+    #[rstest]
+    #[case("HOH", 3)]
+    #[case("HOHOHO", 6)]
+    fn solves_part2_examples(#[case] molecule: &str, #[case] steps: usize) {
+        let input = parse(&format!("{}\n{}\n", REPLACEMENTS, molecule)).unwrap();
+        assert_eq!(part2(&input), steps);
+    }
+
+    #[test]
+    fn rejects_missing_molecule() {
+        assert_eq!(
+            parse("H => HO\n\n").err(),
+            Some(ParseError::new(3, 1, "expected the medicine molecule"))
+        );
+    }
+}
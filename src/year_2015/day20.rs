@@ -0,0 +1,60 @@
+use crate::parsing::parse_at;
+use crate::ParseError;
+
+crate::solver!(2015, 20, u32);
+
+pub fn part1(presents: &u32) -> usize {
+    first_house(*presents, 10, None)
+}
+
+pub fn part2(presents: &u32) -> usize {
+    first_house(*presents, 11, Some(50))
+}
+
+/// The lowest house number that gets at least `presents`, when every elf delivers `per_elf`
+/// presents to each house that is a multiple of its number, up to `limit` houses.
+fn first_house(presents: u32, per_elf: u32, limit: Option<usize>) -> usize {
+    // Elf `n` alone delivers enough to house `n` once `n * per_elf` reaches the target.
+    let houses = (presents / per_elf) as usize + 1;
+    let mut delivered = vec![0; houses + 1];
+    for elf in 1..=houses {
+        let visits = (elf..=houses)
+            .step_by(elf)
+            .take(limit.unwrap_or(usize::MAX));
+        for house in visits {
+            delivered[house] += elf as u32 * per_elf;
+        }
+    }
+    (1..=houses)
+        .find(|house| delivered[*house] >= presents)
+        .expect("a house that gets enough presents")
+}
+
+pub fn parse(input: &str) -> Result<u32, ParseError> {
+    parse_at(input, input.trim(), "a number of presents")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(10, 1)]
+    #[case(30, 2)]
+    #[case(70, 4)]
+    #[case(120, 6)]
+    #[case(130, 8)]
+    #[case(150, 8)]
+    fn solves_part1_examples(#[case] presents: u32, #[case] house: usize) {
+        assert_eq!(part1(&presents), house);
+    }
+
+    #[rstest]
+    #[case(None, 6)]
+    #[case(Some(2), 8)]
+    #[case(Some(1), 10)]
+    fn stops_after_limit(#[case] limit: Option<usize>, #[case] house: usize) {
+        assert_eq!(first_house(110, 11, limit), house);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::parsing::{parse_at, split_once_at};
+use crate::ParseError;
+use itertools::Itertools;
+
+crate::solver!(2015, 21, Fighter);
+
+const HIT_POINTS: u32 = 100;
+
+/// Cost, damage and armor of the items in the shop.
+type Item = (u32, u32, u32);
+
+const WEAPONS: [Item; 5] = [(8, 4, 0), (10, 5, 0), (25, 6, 0), (40, 7, 0), (74, 8, 0)];
+const ARMOR: [Item; 5] = [(13, 0, 1), (31, 0, 2), (53, 0, 3), (75, 0, 4), (102, 0, 5)];
+const RINGS: [Item; 6] = [
+    (25, 1, 0),
+    (50, 2, 0),
+    (100, 3, 0),
+    (20, 0, 1),
+    (40, 0, 2),
+    (80, 0, 3),
+];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Fighter {
+    hit_points: u32,
+    damage: u32,
+    armor: u32,
+}
+
+impl Fighter {
+    /// Number of turns `self` needs to defeat `other`.
+    fn turns_to_defeat(&self, other: &Fighter) -> u32 {
+        let damage = self.damage.saturating_sub(other.armor).max(1);
+        other.hit_points.div_ceil(damage)
+    }
+}
+
+pub fn part1(boss: &Fighter) -> u32 {
+    loadouts()
+        .filter(|(_, player)| wins(player, boss))
+        .map(|(cost, _)| cost)
+        .min()
+        .expect("a loadout that wins")
+}
+
+pub fn part2(boss: &Fighter) -> u32 {
+    loadouts()
+        .filter(|(_, player)| !wins(player, boss))
+        .map(|(cost, _)| cost)
+        .max()
+        .expect("a loadout that loses")
+}
+
+/// Whether the player, who attacks first, wins the fight.
+fn wins(player: &Fighter, boss: &Fighter) -> bool {
+    player.turns_to_defeat(boss) <= boss.turns_to_defeat(player)
+}
+
+/// The cost of every allowed combination of items and the player wearing them.
+fn loadouts() -> impl Iterator<Item = (u32, Fighter)> {
+    let armor = [None].into_iter().chain(ARMOR.map(Some));
+    let rings = (0..=2).flat_map(|count| RINGS.into_iter().combinations(count));
+    WEAPONS
+        .into_iter()
+        .cartesian_product(armor)
+        .cartesian_product(rings.collect::<Vec<_>>())
+        .map(|((weapon, armor), rings)| {
+            let items = [weapon].into_iter().chain(armor).chain(rings);
+            items.fold(
+                (
+                    0,
+                    Fighter {
+                        hit_points: HIT_POINTS,
+                        damage: 0,
+                        armor: 0,
+                    },
+                ),
+                |(cost, player), (price, damage, armor)| {
+                    (
+                        cost + price,
+                        Fighter {
+                            damage: player.damage + damage,
+                            armor: player.armor + armor,
+                            ..player
+                        },
+                    )
+                },
+            )
+        })
+}
+
+pub fn parse(input: &str) -> Result<Fighter, ParseError> {
+    let mut stats = input.lines().map(|line| split_once_at(input, line, ": "));
+    let mut stat = |name: &str| {
+        let (found, value) = stats
+            .next()
+            .unwrap_or_else(|| Ok((&input[input.len()..], "")))?;
+        if found != name {
+            return Err(ParseError::at(
+                input,
+                found,
+                format!("expected {:?}, found {:?}", name, found),
+            ));
+        }
+        parse_at(input, value, "a whole number")
+    };
+    Ok(Fighter {
+        hit_points: stat("Hit Points")?,
+        damage: stat("Damage")?,
+        armor: stat("Armor")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fights_example() {
+        let player = Fighter {
+            hit_points: 8,
+            damage: 5,
+            armor: 5,
+        };
+        let boss = Fighter {
+            hit_points: 12,
+            damage: 7,
+            armor: 2,
+        };
+        assert_eq!(player.turns_to_defeat(&boss), 4);
+        assert_eq!(boss.turns_to_defeat(&player), 4);
+        assert!(wins(&player, &boss));
+        assert!(!wins(
+            &player,
+            &Fighter {
+                hit_points: 13,
+                ..boss
+            }
+        ));
+    }
+
+    #[test]
+    fn counts_loadouts() {
+        // Five weapons, six armor choices and 22 ways to pick up to two rings.
+        assert_eq!(loadouts().count(), 5 * 6 * 22);
+        assert_eq!(loadouts().map(|(cost, _)| cost).min(), Some(8));
+    }
+
+    #[test]
+    fn parses_boss() {
+        assert_eq!(
+            parse("Hit Points: 104\nDamage: 8\nArmor: 1\n"),
+            Ok(Fighter {
+                hit_points: 104,
+                damage: 8,
+                armor: 1
+            })
+        );
+        assert_eq!(
+            parse("Hit Points: 104\nDamage: 8\n"),
+            Err(ParseError::new(3, 1, "expected \"Armor\", found \"\""))
+        );
+    }
+}
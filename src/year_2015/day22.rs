@@ -0,0 +1,220 @@
+use crate::parsing::{parse_at, split_once_at};
+use crate::search;
+use crate::ParseError;
+
+crate::solver!(2015, 22, Boss);
+
+const HIT_POINTS: i32 = 50;
+const MANA: u32 = 500;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Boss {
+    hit_points: i32,
+    damage: i32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Spell {
+    MagicMissile,
+    Drain,
+    Shield,
+    Poison,
+    Recharge,
+}
+
+impl Spell {
+    const ALL: [Spell; 5] = [
+        Spell::MagicMissile,
+        Spell::Drain,
+        Spell::Shield,
+        Spell::Poison,
+        Spell::Recharge,
+    ];
+
+    fn cost(self) -> u32 {
+        match self {
+            Spell::MagicMissile => 53,
+            Spell::Drain => 73,
+            Spell::Shield => 113,
+            Spell::Poison => 173,
+            Spell::Recharge => 229,
+        }
+    }
+}
+
+/// The fight at the start of a turn of the player.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+struct Fight {
+    hit_points: i32,
+    mana: u32,
+    boss_hit_points: i32,
+    /// Turns left of each effect.
+    shield: u8,
+    poison: u8,
+    recharge: u8,
+}
+
+impl Fight {
+    /// Applies the active effects, returns the armor of the player during this turn.
+    fn apply_effects(&mut self) -> i32 {
+        if self.poison > 0 {
+            self.boss_hit_points -= 3;
+            self.poison -= 1;
+        }
+        if self.recharge > 0 {
+            self.mana += 101;
+            self.recharge -= 1;
+        }
+        if self.shield > 0 {
+            self.shield -= 1;
+            7
+        } else {
+            0
+        }
+    }
+
+    /// Casts a spell, unless the player cannot afford it or its effect is still active.
+    fn cast(&mut self, spell: Spell) -> Option<()> {
+        self.mana = self.mana.checked_sub(spell.cost())?;
+        match spell {
+            Spell::MagicMissile => self.boss_hit_points -= 4,
+            Spell::Drain => {
+                self.boss_hit_points -= 2;
+                self.hit_points += 2;
+            }
+            Spell::Shield if self.shield == 0 => self.shield = 6,
+            Spell::Poison if self.poison == 0 => self.poison = 6,
+            Spell::Recharge if self.recharge == 0 => self.recharge = 5,
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn is_won(&self) -> bool {
+        self.boss_hit_points <= 0
+    }
+
+    /// The fight after the player casts `spell` and the boss attacks, unless the player loses.
+    fn round(mut self, spell: Spell, boss: &Boss, hard: bool) -> Option<Fight> {
+        if hard {
+            self.hit_points -= 1;
+            if self.hit_points <= 0 {
+                return None;
+            }
+        }
+        self.apply_effects();
+        if self.is_won() {
+            return Some(self);
+        }
+        self.cast(spell)?;
+        let armor = self.apply_effects();
+        if self.is_won() {
+            return Some(self);
+        }
+        self.hit_points -= (boss.damage - armor).max(1);
+        (self.hit_points > 0).then_some(self)
+    }
+}
+
+pub fn part1(boss: &Boss) -> u32 {
+    least_mana(boss, HIT_POINTS, MANA, false).expect("a way to win")
+}
+
+pub fn part2(boss: &Boss) -> u32 {
+    least_mana(boss, HIT_POINTS, MANA, true).expect("a way to win")
+}
+
+/// The least mana the player can spend and still win, losing a hit point every turn if `hard`.
+fn least_mana(boss: &Boss, hit_points: i32, mana: u32, hard: bool) -> Option<u32> {
+    let start = Fight {
+        hit_points,
+        mana,
+        boss_hit_points: boss.hit_points,
+        shield: 0,
+        poison: 0,
+        recharge: 0,
+    };
+    let successors = |fight: &Fight| {
+        let fight = *fight;
+        Spell::ALL.into_iter().filter_map(move |spell| {
+            // The round checks that the spell is affordable once Recharge has added its mana.
+            Some((fight.round(spell, boss, hard)?, spell.cost()))
+        })
+    };
+    search::dijkstra(start, successors, Fight::is_won).map(|(_, mana)| mana)
+}
+
+pub fn parse(input: &str) -> Result<Boss, ParseError> {
+    let mut stats = input.lines().map(|line| split_once_at(input, line, ": "));
+    let mut stat = |name: &str| {
+        let (found, value) = stats
+            .next()
+            .unwrap_or_else(|| Ok((&input[input.len()..], "")))?;
+        if found != name {
+            return Err(ParseError::at(
+                input,
+                found,
+                format!("expected {:?}, found {:?}", name, found),
+            ));
+        }
+        parse_at(input, value, "a whole number")
+    };
+    Ok(Boss {
+        hit_points: stat("Hit Points")?,
+        damage: stat("Damage")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    // Poison, then Magic Missile.
+    #[case(13, 173 + 53)]
+    // Recharge, Shield, Drain, Poison, then Magic Missile.
+    #[case(14, 229 + 113 + 73 + 173 + 53)]
+    fn solves_examples(#[case] boss_hit_points: i32, #[case] mana: u32) {
+        let boss = Boss {
+            hit_points: boss_hit_points,
+            damage: 8,
+        };
+        assert_eq!(least_mana(&boss, 10, 250, false), Some(mana));
+    }
+
+    #[test]
+    fn loses_hit_points_in_hard_mode() {
+        let boss = Boss {
+            hit_points: 13,
+            damage: 8,
+        };
+        assert_eq!(least_mana(&boss, 11, 250, true), Some(173 + 53));
+        assert_eq!(least_mana(&boss, 10, 250, true), None);
+    }
+
+    // Both need a spell that only the mana from that turn's Recharge tick pays for.
+    #[rstest]
+    #[case(62, 6, false, 1013)]
+    #[case(62, 5, true, 1013)]
+    fn spends_recharged_mana(
+        #[case] hit_points: i32,
+        #[case] damage: i32,
+        #[case] hard: bool,
+        #[case] mana: u32,
+    ) {
+        let boss = Boss { hit_points, damage };
+        assert_eq!(least_mana(&boss, HIT_POINTS, MANA, hard), Some(mana));
+    }
+
+    #[test]
+    fn parses_boss() {
+        assert_eq!(
+            parse("Hit Points: 71\nDamage: 10\n"),
+            Ok(Boss {
+                hit_points: 71,
+                damage: 10
+            })
+        );
+    }
+}
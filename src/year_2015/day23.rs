@@ -0,0 +1,176 @@
+use crate::parsing::{parse_at, split_once_at};
+use crate::vm::{self, Flow, Machine};
+use crate::ParseError;
+use std::fmt::{Display, Formatter};
+
+crate::solver!(2015, 23, Vec<Instruction>);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Instruction {
+    Half(usize),
+    Triple(usize),
+    Increment(usize),
+    Jump(isize),
+    JumpIfEven(usize, isize),
+    JumpIfOne(usize, isize),
+}
+
+pub fn part1(program: &[Instruction]) -> u64 {
+    run(program, [0, 0])[1]
+}
+
+pub fn part2(program: &[Instruction]) -> u64 {
+    run(program, [1, 0])[1]
+}
+
+impl vm::Instruction for Instruction {
+    type Value = u64;
+
+    fn execute(&self, registers: &mut [u64], _: &mut Vec<u64>) -> Flow {
+        match *self {
+            Instruction::Half(r) => registers[r] /= 2,
+            Instruction::Triple(r) => registers[r] *= 3,
+            Instruction::Increment(r) => registers[r] += 1,
+            Instruction::Jump(offset) => return Flow::Jump(offset),
+            Instruction::JumpIfEven(r, offset) if registers[r].is_multiple_of(2) => {
+                return Flow::Jump(offset)
+            }
+            Instruction::JumpIfOne(r, offset) if registers[r] == 1 => return Flow::Jump(offset),
+            Instruction::JumpIfEven(..) | Instruction::JumpIfOne(..) => {}
+        }
+        Flow::Next
+    }
+
+    fn branch(&self) -> Option<Flow> {
+        match *self {
+            Instruction::Jump(offset)
+            | Instruction::JumpIfEven(_, offset)
+            | Instruction::JumpIfOne(_, offset) => Some(Flow::Jump(offset)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = |r: usize| ["a", "b"][r];
+        match *self {
+            Instruction::Half(r) => write!(f, "hlf {}", name(r)),
+            Instruction::Triple(r) => write!(f, "tpl {}", name(r)),
+            Instruction::Increment(r) => write!(f, "inc {}", name(r)),
+            Instruction::Jump(offset) => write!(f, "jmp {:+}", offset),
+            Instruction::JumpIfEven(r, offset) => write!(f, "jie {}, {:+}", name(r), offset),
+            Instruction::JumpIfOne(r, offset) => write!(f, "jio {}, {:+}", name(r), offset),
+        }
+    }
+}
+
+/// The registers when the program leaves its instructions.
+fn run(program: &[Instruction], registers: [u64; 2]) -> [u64; 2] {
+    let mut machine = Machine::new(program, registers);
+    machine.run();
+    machine.registers().try_into().expect("registers a and b")
+}
+
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (operation, operands) = split_once_at(input, line, " ")?;
+            let register = |register: &str| match register {
+                "a" => Ok(0),
+                "b" => Ok(1),
+                _ => Err(ParseError::at(
+                    input,
+                    register,
+                    format!("expected register a or b, found {:?}", register),
+                )),
+            };
+            let offset =
+                |offset: &str| parse_at(input, offset.trim_start_matches('+'), "an offset");
+            let conditional = || {
+                let (r, jump) = split_once_at(input, operands, ", ")?;
+                Ok::<_, ParseError>((register(r)?, offset(jump)?))
+            };
+            Ok(match operation {
+                "hlf" => Instruction::Half(register(operands)?),
+                "tpl" => Instruction::Triple(register(operands)?),
+                "inc" => Instruction::Increment(register(operands)?),
+                "jmp" => Instruction::Jump(offset(operands)?),
+                "jie" => {
+                    let (r, jump) = conditional()?;
+                    Instruction::JumpIfEven(r, jump)
+                }
+                "jio" => {
+                    let (r, jump) = conditional()?;
+                    Instruction::JumpIfOne(r, jump)
+                }
+                _ => {
+                    return Err(ParseError::at(
+                        input,
+                        operation,
+                        format!("expected an instruction, found {:?}", operation),
+                    ))
+                }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+inc a
+jio a, +2
+tpl a
+inc a
+";
+
+    #[test]
+    fn runs_example() {
+        assert_eq!(run(&parse(EXAMPLE).unwrap(), [0, 0]), [2, 0]);
+    }
+
+    #[test]
+    fn jumps_out_of_the_program() {
+        let program = parse("inc b\njmp -2\ninc b\n").unwrap();
+        assert_eq!(run(&program, [0, 0]), [0, 1]);
+        let program = parse("jio a, +2\ninc b\ninc b\n").unwrap();
+        assert_eq!(run(&program, [1, 0]), [1, 1]);
+    }
+
+    #[test]
+    fn disassembles_example() {
+        assert_eq!(
+            vm::disassemble(&parse(EXAMPLE).unwrap()),
+            "\
+0  inc a
+1  jio a, +2  jumps to 3
+2  tpl a
+3  inc a      from 1
+"
+        );
+    }
+
+    #[test]
+    fn parses_instructions() {
+        assert_eq!(
+            parse("hlf b\njie a, -3\njmp +7\n"),
+            Ok(vec![
+                Instruction::Half(1),
+                Instruction::JumpIfEven(0, -3),
+                Instruction::Jump(7)
+            ])
+        );
+        assert_eq!(
+            parse("inc c"),
+            Err(ParseError::new(
+                1,
+                5,
+                "expected register a or b, found \"c\""
+            ))
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use crate::parsing::parse_at;
+use crate::ParseError;
+use itertools::Itertools;
+
+crate::solver!(2015, 24, Vec<u64>);
+
+pub fn part1(packages: &[u64]) -> u64 {
+    balance(packages, 3).expect("a way to balance the sleigh")
+}
+
+pub fn part2(packages: &[u64]) -> u64 {
+    balance(packages, 4).expect("a way to balance the sleigh")
+}
+
+/// Quantum entanglement of the passenger compartment when the packages are split into `groups`
+/// of equal weight, with as few packages as possible in front and the lowest entanglement.
+fn balance(packages: &[u64], groups: u64) -> Option<u64> {
+    let total = packages.iter().sum::<u64>();
+    if total % groups != 0 {
+        return None;
+    }
+    let target = total / groups;
+    (1..=packages.len()).find_map(|count| {
+        let mut candidates = (0..packages.len())
+            .combinations(count)
+            .filter(|front| front.iter().map(|i| packages[*i]).sum::<u64>() == target)
+            .map(|front| {
+                let entanglement = front.iter().map(|i| packages[*i]).product::<u64>();
+                (entanglement, front)
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_unstable();
+        candidates.into_iter().find_map(|(entanglement, front)| {
+            let rest = (0..packages.len())
+                .filter(|i| !front.contains(i))
+                .map(|i| packages[i])
+                .collect::<Vec<_>>();
+            can_split(&rest, groups - 1, target).then_some(entanglement)
+        })
+    })
+}
+
+/// Whether `packages` can be split into `groups` of weight `target` each.
+fn can_split(packages: &[u64], groups: u64, target: u64) -> bool {
+    fn fill(packages: &[u64], used: &mut [bool], groups: u64, target: u64, load: u64) -> bool {
+        if groups <= 1 {
+            return true;
+        }
+        if load == target {
+            return fill(packages, used, groups - 1, target, 0);
+        }
+        // The first unused package has to go somewhere, so it starts the group.
+        let start = if load == 0 {
+            match used.iter().position(|used| !used) {
+                Some(first) => first,
+                None => return false,
+            }
+        } else {
+            0
+        };
+        for i in start..packages.len() {
+            if !used[i] && load + packages[i] <= target {
+                used[i] = true;
+                if fill(packages, used, groups, target, load + packages[i]) {
+                    return true;
+                }
+                used[i] = false;
+                if load == 0 {
+                    break;
+                }
+            }
+        }
+        false
+    }
+    packages.iter().sum::<u64>() == groups * target
+        && fill(
+            packages,
+            &mut vec![false; packages.len()],
+            groups,
+            target,
+            0,
+        )
+}
+
+pub fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    input
+        .split_whitespace()
+        .map(|weight| parse_at(input, weight, "a package weight"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1\n2\n3\n4\n5\n7\n8\n9\n10\n11\n";
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 99);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 44);
+    }
+
+    #[test]
+    fn checks_the_rest_can_be_split() {
+        assert!(can_split(&[1, 4, 5], 2, 5));
+        assert!(can_split(&[2, 3, 3, 2], 2, 5));
+        assert!(!can_split(&[1, 1, 8], 2, 5));
+        // 5 alone weighs a third, but the twos cannot make up the other groups.
+        assert_eq!(balance(&[5, 2, 2, 2, 2, 2], 3), None);
+    }
+}
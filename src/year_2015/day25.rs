@@ -0,0 +1,88 @@
+use crate::parsing::parse_at;
+use crate::ParseError;
+
+crate::solver!(2015, 25, (u64, u64), parts = 1);
+
+const FIRST_CODE: u64 = 20151125;
+const MULTIPLIER: u64 = 252533;
+const MODULUS: u64 = 33554393;
+
+pub fn part1(&(row, column): &(u64, u64)) -> u64 {
+    code(row, column)
+}
+
+/// The code at `row` and `column` of the manual, which is filled diagonal by diagonal.
+fn code(row: u64, column: u64) -> u64 {
+    let diagonal = row + column - 1;
+    let index = diagonal * (diagonal - 1) / 2 + column - 1;
+    FIRST_CODE * power(MULTIPLIER, index) % MODULUS
+}
+
+/// `base` to the power of `exponent`, modulo [`MODULUS`].
+fn power(mut base: u64, mut exponent: u64) -> u64 {
+    let mut result = 1;
+    while exponent > 0 {
+        if exponent % 2 == 1 {
+            result = result * base % MODULUS;
+        }
+        base = base * base % MODULUS;
+        exponent /= 2;
+    }
+    result
+}
+
+pub fn parse(input: &str) -> Result<(u64, u64), ParseError> {
+    let position = input
+        .trim_end()
+        .strip_suffix('.')
+        .and_then(|text| text.split_once("at row "))
+        .and_then(|(_, position)| position.split_once(", column "));
+    let Some((row, column)) = position else {
+        return Err(ParseError::at(
+            input,
+            input,
+            "expected 'Enter the code at row <row>, column <column>.'",
+        ));
+    };
+    let number = |value| match parse_at(input, value, "a row or column")? {
+        0 => Err(ParseError::at(input, value, "expected a number from 1 on")),
+        number => Ok(number),
+    };
+    Ok((number(row)?, number(column)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1, 1, 20151125)]
+    #[case(2, 1, 31916031)]
+    #[case(1, 2, 18749137)]
+    #[case(4, 2, 32451966)]
+    #[case(5, 1, 77061)]
+    #[case(1, 6, 33511524)]
+    #[case(6, 6, 27995004)]
+    fn finds_codes(#[case] row: u64, #[case] column: u64, #[case] expected: u64) {
+        assert_eq!(code(row, column), expected);
+    }
+
+    #[test]
+    fn parses_position() {
+        let input = "To continue, please consult the code grid in the manual.  \
+                     Enter the code at row 2947, column 3029.\n";
+        assert_eq!(parse(input), Ok((2947, 3029)));
+        assert_eq!(
+            parse("Enter the code at row 0, column 3.").err(),
+            Some(ParseError::new(1, 23, "expected a number from 1 on"))
+        );
+    }
+
+    #[test]
+    fn has_only_one_part() {
+        use crate::Solver;
+        assert_eq!(Day::PARTS, 1);
+        assert_eq!(Day::part2(&(1, 1)).to_string(), "-");
+    }
+}
@@ -1,5 +1,9 @@
+use crate::parsing::parse_at;
+use crate::ParseError;
 use std::collections::HashMap;
 
+crate::solver!(2024, 1, Input);
+
 pub fn part1((left, right): &Input) -> u32 {
     let mut left = left.clone();
     left.sort_unstable();
@@ -21,22 +25,21 @@ pub fn part2((left, right): &Input) -> u32 {
 
 type Input = (Vec<u32>, Vec<u32>);
 
-pub fn parse(input: &str) -> Input {
+pub fn parse(input: &str) -> Result<Input, ParseError> {
     input
         .lines()
-        .filter_map(|line| {
+        .map(|line| {
             if let [left, right] = line
                 .split_whitespace()
-                .map(|n| n.parse::<u32>().expect("numbers"))
-                .take(2)
-                .collect::<Vec<_>>()[..]
+                .map(|n| parse_at::<u32>(input, n, "location ID"))
+                .collect::<Result<Vec<_>, _>>()?[..]
             {
-                Some((left, right))
+                Ok((left, right))
             } else {
-                None
+                Err(ParseError::at(input, line, "expected two location IDs"))
             }
         })
-        .unzip()
+        .collect()
 }
 
 #[cfg(test)]
@@ -54,17 +57,17 @@ mod tests {
     fn parses_example_lists() {
         assert_eq!(
             parse(EXAMPLE),
-            (vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3])
+            Ok((vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]))
         );
     }
     #[test]
     fn solves_part1() {
-        let input = parse(EXAMPLE);
+        let input = parse(EXAMPLE).unwrap();
         assert_eq!(part1(&input), 11);
     }
     #[test]
     fn solves_part2() {
-        let input = parse(EXAMPLE);
+        let input = parse(EXAMPLE).unwrap();
         assert_eq!(part2(&input), 31);
     }
 }
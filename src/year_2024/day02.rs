@@ -1,3 +1,8 @@
+use crate::parsing::parse_at;
+use crate::ParseError;
+
+crate::solver!(2024, 2, Vec<Vec<i32>>);
+
 pub fn part1(reports: &[impl AsRef<[i32]>]) -> usize {
     reports
         .iter()
@@ -12,12 +17,12 @@ pub fn part2(reports: &[impl AsRef<[i32]>]) -> usize {
         .count()
 }
 
-pub fn parse(input: &str) -> Vec<Vec<i32>> {
+pub fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
     input
         .lines()
         .map(|line| {
             line.split_whitespace()
-                .map(|x| x.parse::<i32>().expect("Numbers separated by whitespace"))
+                .map(|x| parse_at::<i32>(input, x, "level"))
                 .collect()
         })
         .collect()
@@ -65,7 +70,7 @@ mod tests {
             vec![8, 6, 4, 4, 1],
             vec![1, 3, 6, 7, 9],
         ];
-        assert_eq!(reports, parse(EXAMPLE_INPUT));
+        assert_eq!(Ok(reports), parse(EXAMPLE_INPUT));
     }
 
     #[rstest]
@@ -91,11 +96,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(&parse(EXAMPLE_INPUT)), 2);
+        assert_eq!(part1(&parse(EXAMPLE_INPUT).unwrap()), 2);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(&parse(EXAMPLE_INPUT)), 4);
+        assert_eq!(part2(&parse(EXAMPLE_INPUT).unwrap()), 4);
     }
 }
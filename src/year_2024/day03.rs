@@ -1,5 +1,8 @@
+use crate::ParseError;
 use regex::Regex;
 
+crate::solver!(2024, 3, Vec<Statement>);
+
 pub enum Statement {
     Do,
     Dont,
@@ -34,8 +37,9 @@ pub fn part2(statements: &[Statement]) -> u32 {
         .sum()
 }
 
-pub fn parse(input: &str) -> Vec<Statement> {
-    Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)|do\(\)|don't\(\)")
+pub fn parse(input: &str) -> Result<Vec<Statement>, ParseError> {
+    // Anything that is not a valid instruction is corrupted memory, so parsing cannot fail.
+    Ok(Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)|do\(\)|don't\(\)")
         .expect("valid regex")
         .captures_iter(input)
         .filter_map(|captures| match &captures.get(0).unwrap().as_str()[0..3] {
@@ -57,7 +61,7 @@ pub fn parse(input: &str) -> Vec<Statement> {
             "do(" => Some(Statement::Do),
             _ => None,
         })
-        .collect::<Vec<_>>()
+        .collect::<Vec<_>>())
 }
 
 #[cfg(test)]
@@ -67,18 +71,20 @@ mod tests {
     #[test]
     fn test_part1() {
         assert_eq!(
-            part1(&parse(
-                "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))"
-            )),
+            part1(
+                &parse("xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))")
+                    .unwrap()
+            ),
             161
         );
     }
     #[test]
     fn test_part2() {
         assert_eq!(
-            part2(&parse(
-                "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"
-            )),
+            part2(
+                &parse("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))")
+                    .unwrap()
+            ),
             48
         );
     }
@@ -1,29 +1,21 @@
-use std::collections::HashMap;
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::ParseError;
 
-type Input = HashMap<(i32, i32), char>;
+crate::solver!(2024, 4, Input);
+
+type Input = Grid<char>;
 
 pub fn part1(input: &Input) -> usize {
     input
         .iter()
         .filter(|(_, char)| **char == 'X')
-        .flat_map(|((x, y), _)| {
-            (-1..=1)
-                .flat_map(|dx| {
-                    (-1..=1)
-                        .map(move |dy| (dx, dy))
-                        .filter(|(dx, dy)| (*dx, *dy) != (0, 0))
-                })
-                .filter(move |(dx, dy)| {
-                    if let (Some(m), Some(a), Some(s)) = (
-                        &&input.get(&(*x + *dx, *y + *dy)),
-                        &&input.get(&(*x + 2 * *dx, *y + 2 * *dy)),
-                        &input.get(&(*x + 3 * *dx, *y + 3 * *dy)),
-                    ) {
-                        **m == 'M' && **a == 'A' && **s == 'S'
-                    } else {
-                        false
-                    }
+        .flat_map(|(position, _)| {
+            Point::ADJACENT.into_iter().filter(move |direction| {
+                "MAS".chars().zip(1..).all(|(char, distance)| {
+                    input.get(position + *direction * distance) == Some(&char)
                 })
+            })
         })
         .count()
 }
@@ -31,23 +23,13 @@ pub fn part1(input: &Input) -> usize {
 pub fn part2(input: &Input) -> usize {
     input
         .iter()
-        .filter(|((x, y), char)| {
+        .filter(|(position, char)| {
             **char == 'A'
-                && (-1..=1)
-                    .flat_map(|dx| {
-                        (-1..=1)
-                            .map(move |dy| (dx, dy))
-                            .filter(|(dx, dy)| *dx != 0 && *dy != 0)
-                    })
-                    .filter(move |(dx, dy)| {
-                        if let (Some(m), Some(s)) = (
-                            &&input.get(&(*x - *dx, *y - *dy)),
-                            &&input.get(&(*x + *dx, *y + *dy)),
-                        ) {
-                            **m == 'M' && **s == 'S'
-                        } else {
-                            false
-                        }
+                && Point::DIAGONAL
+                    .into_iter()
+                    .filter(|direction| {
+                        input.get(*position - *direction) == Some(&'M')
+                            && input.get(*position + *direction) == Some(&'S')
                     })
                     .count()
                     == 2
@@ -55,16 +37,10 @@ pub fn part2(input: &Input) -> usize {
         .count()
 }
 
-pub fn parse(input: &str) -> HashMap<(i32, i32), char> {
-    input
-        .lines()
-        .enumerate()
-        .flat_map(|(row, line)| {
-            line.chars()
-                .enumerate()
-                .map(move |(column, char)| ((row as i32, column as i32), char))
-        })
-        .collect()
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    Grid::parse(input, "one of 'X', 'M', 'A' or 'S'", |char| {
+        "XMAS".contains(char).then_some(char)
+    })
 }
 
 #[cfg(test)]
@@ -85,11 +61,11 @@ MXMXAXMASX
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(&parse(EXAMPLE_INPUT)), 18);
+        assert_eq!(part1(&parse(EXAMPLE_INPUT).unwrap()), 18);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(&parse(EXAMPLE_INPUT)), 9);
+        assert_eq!(part2(&parse(EXAMPLE_INPUT).unwrap()), 9);
     }
 }
@@ -1,6 +1,10 @@
+use crate::parsing::{parse_at, split_once_at};
+use crate::ParseError;
 use std::cmp::Ordering;
 use std::collections::HashSet;
 
+crate::solver!(2024, 5, Input);
+
 pub struct Input {
     rules: HashSet<(u32, u32)>,
     updates: Vec<Vec<u32>>,
@@ -51,32 +55,32 @@ pub fn part2(input: &Input) -> u32 {
     input.corrected_updates().map(|x| x[x.len() / 2]).sum()
 }
 
-pub fn parse(input: &str) -> Input {
+pub fn parse(input: &str) -> Result<Input, ParseError> {
     let (rules, updates) = input
         .split_once("\n\n")
-        .expect("Empty line between rule and updates");
+        .ok_or_else(|| ParseError::end(input, "expected empty line between rules and updates"))?;
 
     let rules = rules
         .lines()
         .map(|line| {
-            let (l, r) = line.split_once('|').expect("Rule with '|' as separator");
-            (
-                l.parse::<u32>().expect("Page number"),
-                r.parse::<u32>().expect("Page number"),
-            )
+            let (l, r) = split_once_at(input, line, "|")?;
+            Ok((
+                parse_at::<u32>(input, l, "page number")?,
+                parse_at::<u32>(input, r, "page number")?,
+            ))
         })
-        .collect::<HashSet<(u32, u32)>>();
+        .collect::<Result<HashSet<(u32, u32)>, _>>()?;
 
     let updates = updates
         .lines()
         .map(|line| {
             line.split(',')
-                .map(|page| page.parse::<u32>().expect("Page number"))
-                .collect::<Vec<u32>>()
+                .map(|page| parse_at::<u32>(input, page, "page number"))
+                .collect::<Result<Vec<u32>, _>>()
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, _>>()?;
 
-    Input { rules, updates }
+    Ok(Input { rules, updates })
 }
 
 #[cfg(test)]
@@ -115,7 +119,7 @@ mod tests {
 
     #[test]
     fn parse_example() {
-        let parsed = parse(EXAMPLE_INPUT);
+        let parsed = parse(EXAMPLE_INPUT).unwrap();
         assert_eq!(parsed.rules.len(), 21);
         assert!(parsed.rules.contains(&(47, 53)));
         assert!(parsed.rules.contains(&(53, 13)));
@@ -125,16 +129,36 @@ mod tests {
         assert_eq!(parsed.updates[5], vec![97, 13, 75, 29, 47]);
     }
 
+    #[test]
+    fn rejects_invalid_input() {
+        assert_eq!(
+            parse("47|53\n97-13\n\n75,47").err(),
+            Some(ParseError::new(2, 1, "expected \"|\" in \"97-13\""))
+        );
+        assert_eq!(
+            parse("47|53\n\n75,47\n97,x").err(),
+            Some(ParseError::new(4, 4, "expected page number, found \"x\""))
+        );
+        assert_eq!(
+            parse("47|53\n97|13").err(),
+            Some(ParseError::new(
+                2,
+                6,
+                "expected empty line between rules and updates"
+            ))
+        );
+    }
+
     #[test]
     fn is_valid() {
-        let input = parse(EXAMPLE_INPUT);
+        let input = parse(EXAMPLE_INPUT).unwrap();
 
         assert!(!input.is_valid(&[75, 97, 47, 61, 53]));
     }
 
     #[test]
     fn validates_updates() {
-        let input = parse(EXAMPLE_INPUT);
+        let input = parse(EXAMPLE_INPUT).unwrap();
         let valid_updates = input.valid_updates().cloned().collect::<Vec<_>>();
         assert_eq!(
             valid_updates,
@@ -148,12 +172,12 @@ mod tests {
 
     #[test]
     fn solves_part1() {
-        assert_eq!(part1(&parse(EXAMPLE_INPUT)), 143);
+        assert_eq!(part1(&parse(EXAMPLE_INPUT).unwrap()), 143);
     }
 
     #[test]
     fn corrects_order() {
-        let input = parse(EXAMPLE_INPUT);
+        let input = parse(EXAMPLE_INPUT).unwrap();
         let mut update = vec![75, 97, 47, 61, 53];
         input.fix_order(&mut update);
         assert_eq!(update, vec![97, 75, 47, 61, 53]);
@@ -161,7 +185,7 @@ mod tests {
 
     #[test]
     fn corrects_incorrect_updates() {
-        let input = parse(EXAMPLE_INPUT);
+        let input = parse(EXAMPLE_INPUT).unwrap();
 
         assert_eq!(
             input.corrected_updates().collect::<Vec<_>>(),
@@ -175,6 +199,6 @@ mod tests {
 
     #[test]
     fn solves_part2() {
-        assert_eq!(part2(&parse(EXAMPLE_INPUT)), 123);
+        assert_eq!(part2(&parse(EXAMPLE_INPUT).unwrap()), 123);
     }
 }
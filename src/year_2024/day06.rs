@@ -1,35 +1,13 @@
-use std::collections::{HashMap, HashSet};
-use std::hash::Hash;
-
-#[derive(Copy, Clone, Debug, PartialEq, Hash, Eq)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-use Direction::*;
+use crate::cycle;
+use crate::geometry::Direction::{self, *};
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::parallel;
+use crate::render::{Picture, Rgb};
+use crate::ParseError;
+use std::collections::HashSet;
 
-impl Direction {
-    fn turn_right(&self) -> Direction {
-        match self {
-            Up => Right,
-            Right => Down,
-            Down => Left,
-            Left => Up,
-        }
-    }
-
-    fn advance(&self, (row, column): (isize, isize)) -> (isize, isize) {
-        match self {
-            Up => (row - 1, column),
-            Right => (row, column + 1),
-            Down => (row + 1, column),
-            Left => (row, column - 1),
-        }
-    }
-}
+crate::solver!(2024, 6, Input, visualise);
 
 #[derive(Clone, PartialEq)]
 enum Tile {
@@ -39,18 +17,14 @@ enum Tile {
 
 #[derive(Clone)]
 pub struct Input {
-    tiles: HashMap<(isize, isize), Tile>,
-    guard_start_position: (isize, isize),
+    tiles: Grid<Tile>,
+    guard_start_position: Point,
     guard_start_direction: Direction,
 }
 
-fn step(
-    position: (isize, isize),
-    direction: Direction,
-    tiles: &HashMap<(isize, isize), Tile>,
-) -> Option<((isize, isize), Direction)> {
-    let next_position = direction.advance(position);
-    match tiles.get(&next_position) {
+fn step(position: Point, direction: Direction, tiles: &Grid<Tile>) -> Option<(Point, Direction)> {
+    let next_position = position + direction;
+    match tiles.get(next_position) {
         None => None,
         Some(Tile::Empty) => Some((next_position, direction)),
         Some(Tile::Obstacle) => step(position, direction.turn_right(), tiles),
@@ -58,35 +32,83 @@ fn step(
 }
 
 impl Input {
-    fn guard_positions(&self) -> HashSet<(isize, isize)> {
+    fn guard_positions(&self) -> HashSet<Point> {
         self.patrol()
             .map(|(position, _)| position)
             .collect::<HashSet<_>>()
     }
 
-    fn patrol(&self) -> impl Iterator<Item = ((isize, isize), Direction)> + use<'_> {
+    fn patrol(&self) -> impl Iterator<Item = (Point, Direction)> + use<'_> {
         std::iter::successors(
             Some((self.guard_start_position, self.guard_start_direction)),
             |(position, direction)| step(*position, *direction, &self.tiles),
         )
     }
 
-    fn count_cycles(&mut self) -> usize {
-        let mut obstacles = self.guard_positions();
-        obstacles.remove(&self.guard_start_position);
-        obstacles
-            .iter()
-            .filter(|obstacle| {
-                self.tiles.insert(**obstacle, Tile::Obstacle);
-                let has_cycle = {
-                    let fast = self.patrol().step_by(2);
-                    let slow = self.patrol();
-                    fast.zip(slow).skip(1).any(|(fast, slow)| fast == slow)
-                };
-                self.tiles.insert(**obstacle, Tile::Empty);
-                has_cycle
-            })
-            .count()
+    /// Counts the positions where a single new obstacle makes the guard walk in a loop.
+    ///
+    /// Each candidate is simulated from the state just before the guard first runs into it,
+    /// jumping from obstacle to obstacle instead of walking tile by tile.
+    fn count_cycles(&self) -> usize {
+        let jumps = Jumps::new(&self.tiles);
+        let mut seen = self.tiles.map(|_| false);
+        seen[self.guard_start_position] = true;
+        let candidates = self
+            .patrol()
+            .zip(self.patrol().skip(1))
+            .filter(|(_, (position, _))| !std::mem::replace(&mut seen[*position], true))
+            .collect::<Vec<_>>();
+        parallel::count(&candidates, |(before, (obstacle, _))| {
+            cycle::brent(*before, |state| jumps.next(*state, *obstacle)).is_some()
+        })
+    }
+}
+
+/// For every cell and direction, where the guard stops in front of the next obstacle, or `None`
+/// if it leaves the map.
+struct Jumps(Grid<[Option<Point>; 4]>);
+
+impl Jumps {
+    fn new(tiles: &Grid<Tile>) -> Jumps {
+        let mut jumps = tiles.map(|_| [None; 4]);
+        // Visits the cell ahead before the cell itself, so its jump is already known.
+        let mut positions = tiles.positions().collect::<Vec<_>>();
+        for directions in [[Up, Left], [Down, Right]] {
+            for direction in directions {
+                for position in &positions {
+                    let ahead = *position + direction;
+                    jumps[*position][direction as usize] = match tiles.get(ahead) {
+                        None => None,
+                        Some(Tile::Obstacle) => Some(*position),
+                        Some(Tile::Empty) => jumps[ahead][direction as usize],
+                    };
+                }
+            }
+            positions.reverse();
+        }
+        Jumps(jumps)
+    }
+
+    /// The state after walking up to the next obstacle, including the extra `obstacle`, and
+    /// turning right.
+    fn next(
+        &self,
+        (position, direction): (Point, Direction),
+        obstacle: Point,
+    ) -> Option<(Point, Direction)> {
+        let stop = self.0[position][direction as usize];
+        let offset = obstacle - position;
+        let step = direction.offset();
+        let distance = offset.row * step.row + offset.column * step.column;
+        let blocked = offset == step * distance
+            && distance > 0
+            && stop.is_none_or(|stop| distance as usize <= stop.manhattan(position));
+        let stop = if blocked {
+            Some(obstacle + direction.opposite())
+        } else {
+            stop
+        };
+        stop.map(|stop| (stop, direction.turn_right()))
     }
 }
 
@@ -95,34 +117,49 @@ pub fn part1(input: &Input) -> usize {
 }
 
 pub fn part2(input: &Input) -> usize {
-    let mut clone: Input = input.clone();
-    clone.count_cycles()
+    input.count_cycles()
 }
 
-pub fn parse(input: &str) -> Input {
-    let mut guard: Option<(usize, usize, Direction)> = None;
-    let mut tiles = HashMap::new();
-    input.lines().enumerate().for_each(|(row, line)| {
-        line.chars().enumerate().for_each(|(column, char)| {
-            let tile = match char {
-                '#' => Tile::Obstacle,
-                '^' => {
-                    guard.replace((row, column, Up));
-                    Tile::Empty
-                }
-                _ => Tile::Empty,
-            };
-            tiles.insert((row as isize, column as isize), tile);
-        })
+/// Draws the patrol, one picture each time the guard turns or leaves.
+pub fn visualise(input: &Input) -> Vec<Picture> {
+    let mut picture = Picture::new(&input.tiles, |tile| match tile {
+        Tile::Empty => '.',
+        Tile::Obstacle => '#',
     });
-    let (guard_start_position, guard_start_direction) = guard
-        .map(|(r, c, d)| ((r as isize, c as isize), d))
-        .expect("Guard on map");
-    Input {
-        tiles,
-        guard_start_position,
-        guard_start_direction,
+    let mut pictures = Vec::new();
+    let mut patrol = input.patrol().peekable();
+    while let Some((position, direction)) = patrol.next() {
+        picture.paint([position], Rgb::BLUE);
+        if patrol.peek().is_none_or(|(_, next)| *next != direction) {
+            let mut frame = picture.clone();
+            let guard = match direction {
+                Up => '^',
+                Right => '>',
+                Down => 'v',
+                Left => '<',
+            };
+            frame.label(position, guard).paint([position], Rgb::YELLOW);
+            pictures.push(frame);
+        }
     }
+    pictures
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let map = Grid::parse(input, "'.', '#' or '^'", |char| {
+        ".#^".contains(char).then_some(char)
+    })?;
+    let guard_start_position = map
+        .find(|char| *char == '^')
+        .ok_or_else(|| ParseError::end(input, "expected a guard '^' on the map"))?;
+    Ok(Input {
+        tiles: map.map(|char| match char {
+            '#' => Tile::Obstacle,
+            _ => Tile::Empty,
+        }),
+        guard_start_position,
+        guard_start_direction: Up,
+    })
 }
 
 #[cfg(test)]
@@ -143,10 +180,10 @@ mod tests {
 
     #[test]
     fn parses_input() {
-        let input = parse(EXAMPLE_INPUT);
+        let input = parse(EXAMPLE_INPUT).unwrap();
         assert_eq!(input.guard_start_direction, Up);
-        assert_eq!(input.guard_start_position, (6, 4));
-        assert_eq!(input.tiles.len(), 100);
+        assert_eq!(input.guard_start_position, Point::new(6, 4));
+        assert_eq!((input.tiles.width(), input.tiles.height()), (10, 10));
         assert_eq!(
             input
                 .tiles
@@ -157,35 +194,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rejects_map_without_guard() {
+        assert_eq!(
+            parse("..#\n...\n").err(),
+            Some(ParseError::new(3, 1, "expected a guard '^' on the map"))
+        );
+        assert_eq!(
+            parse("..#\n.^x\n").err(),
+            Some(ParseError::new(2, 3, "expected '.', '#' or '^', found 'x'"))
+        );
+    }
+
     #[test]
     fn patrols_til_first_obstruction() {
-        let input = parse(EXAMPLE_INPUT);
+        let input = parse(EXAMPLE_INPUT).unwrap();
         let positions = input.patrol().take(6).collect::<Vec<_>>();
         assert_eq!(
             positions,
             vec![
-                ((6, 4), Up),
-                ((5, 4), Up),
-                ((4, 4), Up),
-                ((3, 4), Up),
-                ((2, 4), Up),
-                ((1, 4), Up)
+                (Point::new(6, 4), Up),
+                (Point::new(5, 4), Up),
+                (Point::new(4, 4), Up),
+                (Point::new(3, 4), Up),
+                (Point::new(2, 4), Up),
+                (Point::new(1, 4), Up)
             ]
         );
     }
 
     #[test]
     fn turns_left_on_obstruction() {
-        let input = parse(EXAMPLE_INPUT);
-        assert_eq!(input.patrol().nth(6), Some(((1, 5), Right)));
+        let input = parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(input.patrol().nth(6), Some((Point::new(1, 5), Right)));
+    }
+
+    #[test]
+    fn jumps_to_next_obstacle() {
+        let input = parse(EXAMPLE_INPUT).unwrap();
+        let jumps = Jumps::new(&input.tiles);
+        let start = input.guard_start_position;
+        assert_eq!(jumps.0[start][Up as usize], Some(Point::new(1, 4)));
+        assert_eq!(jumps.0[start][Left as usize], Some(Point::new(6, 2)));
+        assert_eq!(jumps.0[start][Down as usize], None);
+        assert_eq!(
+            jumps.next((start, Up), Point::new(10, 10)),
+            Some((Point::new(1, 4), Right))
+        );
+        assert_eq!(
+            jumps.next((start, Up), Point::new(3, 4)),
+            Some((Point::new(4, 4), Right))
+        );
+        assert_eq!(
+            jumps.next((start, Down), Point::new(9, 4)),
+            Some((Point::new(8, 4), Left))
+        );
     }
 
     #[test]
     fn solves_part1() {
-        assert_eq!(part1(&parse(EXAMPLE_INPUT)), 41);
+        assert_eq!(part1(&parse(EXAMPLE_INPUT).unwrap()), 41);
     }
     #[test]
     fn solves_part2() {
-        assert_eq!(part2(&parse(EXAMPLE_INPUT)), 6);
+        assert_eq!(part2(&parse(EXAMPLE_INPUT).unwrap()), 6);
     }
 }
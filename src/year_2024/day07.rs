@@ -1,5 +1,10 @@
+use crate::parallel;
+use crate::parsing::{parse_at, split_once_at};
+use crate::ParseError;
 use std::ops::Add;
 use std::ops::Mul;
+
+crate::solver!(2024, 7, Input);
 type Input = Vec<Vec<u64>>;
 
 pub fn part1(input: &Input) -> u64 {
@@ -11,26 +16,29 @@ pub fn part1(input: &Input) -> u64 {
 }
 
 pub fn part2(input: &Input) -> u64 {
-    input
-        .iter()
-        .filter(|numbers| possible_true(numbers, &[u64::add, u64::mul, concatenate]))
-        .map(|numbers| numbers[0])
-        .sum()
+    parallel::sum(input, |numbers| {
+        if possible_true(numbers, &[u64::add, u64::mul, concatenate]) {
+            numbers[0]
+        } else {
+            0
+        }
+    })
 }
 
-pub fn parse(input: &str) -> Input {
+pub fn parse(input: &str) -> Result<Input, ParseError> {
     input
         .lines()
         .map(|line| {
-            line.split_whitespace()
-                .map(|number| {
-                    number
-                        .strip_suffix(":")
-                        .unwrap_or(number)
-                        .parse::<u64>()
-                        .expect("numbers")
-                })
-                .collect()
+            let (result, operands) = split_once_at(input, line, ":")?;
+            let numbers = std::iter::once(result)
+                .chain(operands.split_whitespace())
+                .map(|number| parse_at::<u64>(input, number, "number"))
+                .collect::<Result<Vec<_>, _>>()?;
+            if numbers.len() < 2 {
+                Err(ParseError::at(input, line, "expected at least one operand"))
+            } else {
+                Ok(numbers)
+            }
         })
         .collect()
 }
@@ -82,6 +90,7 @@ mod tests {
     #[test]
     fn possible_true_examples() {
         let results = parse(EXAMPLE_INPUT)
+            .unwrap()
             .iter()
             .map(|equation| possible_true(equation, &[u64::add, u64::mul]))
             .collect::<Vec<_>>();
@@ -93,11 +102,11 @@ mod tests {
 
     #[test]
     fn solves_example_part1() {
-        assert_eq!(part1(&parse(EXAMPLE_INPUT)), 3749);
+        assert_eq!(part1(&parse(EXAMPLE_INPUT).unwrap()), 3749);
     }
 
     #[test]
     fn solves_example_part2() {
-        assert_eq!(part2(&parse(EXAMPLE_INPUT)), 11387);
+        assert_eq!(part2(&parse(EXAMPLE_INPUT).unwrap()), 11387);
     }
 }
@@ -1,11 +1,22 @@
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::ParseError;
 use itertools::iterate;
 use std::collections::{HashMap, HashSet};
 
+crate::solver!(2024, 8, Input);
+
 #[derive(PartialEq, Debug)]
 pub struct Input {
-    antennas: HashMap<char, Vec<(i32, i32)>>,
-    width: i32,
-    height: i32,
+    antennas: HashMap<char, Vec<Point>>,
+    width: isize,
+    height: isize,
+}
+
+impl Input {
+    fn contains(&self, point: Point) -> bool {
+        (0..self.height).contains(&point.row) && (0..self.width).contains(&point.column)
+    }
 }
 
 pub fn part1(input: &Input) -> usize {
@@ -13,9 +24,7 @@ pub fn part1(input: &Input) -> usize {
         .antennas
         .values()
         .flat_map(|antennas| antinodes(antennas))
-        .filter(|(row, column)| {
-            (0..input.height).contains(row) && (0..input.width).contains(column)
-        })
+        .filter(|antinode| input.contains(*antinode))
         .collect::<HashSet<_>>()
         .len()
 }
@@ -24,64 +33,44 @@ pub fn part2(input: &Input) -> usize {
     input
         .antennas
         .values()
-        .flat_map(|antennas| all_antinodes(antennas, input.height, input.width))
+        .flat_map(|antennas| all_antinodes(antennas, input))
         .collect::<HashSet<_>>()
         .len()
 }
 
-pub fn parse(input: &str) -> Input {
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let map = Grid::parse(input, "'.' or an antenna frequency", |char| {
+        (char == '.' || char.is_alphanumeric()).then_some(char)
+    })?;
     let mut antennas = HashMap::new();
-    let mut width = 0;
-    let mut height = 0;
-    input.lines().enumerate().for_each(|(row, line)| {
-        width = line.len() as i32;
-        height += 1;
-        line.chars()
-            .enumerate()
-            .filter(|(_, char)| char.is_alphanumeric())
-            .for_each(|(column, char)| {
-                antennas
-                    .entry(char)
-                    .or_insert(Vec::new())
-                    .push((row as i32, column as i32));
-            });
-    });
-    Input {
-        antennas,
-        width,
-        height,
+    for (position, char) in map.iter().filter(|(_, char)| **char != '.') {
+        antennas.entry(*char).or_insert(Vec::new()).push(position);
     }
+    Ok(Input {
+        antennas,
+        width: map.width() as isize,
+        height: map.height() as isize,
+    })
 }
 
-fn antinodes(antennas: &[(i32, i32)]) -> impl Iterator<Item = (i32, i32)> + use<'_> {
-    antennas.iter().flat_map(|antenna1 @ (row1, column1)| {
+fn antinodes(antennas: &[Point]) -> impl Iterator<Item = Point> + use<'_> {
+    antennas.iter().flat_map(|antenna1| {
         antennas
             .iter()
             .filter(move |antenna2| antenna1 != *antenna2)
-            .flat_map(move |(row2, column2)| {
-                let (drow, dcolumn) = (row2 - row1, column2 - column1);
-                std::iter::once((row2 + drow, column2 + dcolumn))
-            })
+            .map(move |antenna2| *antenna2 + (*antenna2 - *antenna1))
     })
 }
 
-fn all_antinodes(
-    antennas: &[(i32, i32)],
-    height: i32,
-    width: i32,
-) -> impl Iterator<Item = (i32, i32)> + use<'_> {
-    antennas.iter().flat_map(move |antenna1 @ (row1, column1)| {
+fn all_antinodes<'a>(antennas: &'a [Point], input: &'a Input) -> impl Iterator<Item = Point> + 'a {
+    antennas.iter().flat_map(move |antenna1| {
         antennas
             .iter()
             .filter(move |antenna2| antenna1 != *antenna2)
-            .flat_map(move |(row2, column2)| {
-                let (drow, dcolumn) = (row2 - row1, column2 - column1);
-                iterate((*row2, *column2), move |(row, column)| {
-                    (*row + drow, *column + dcolumn)
-                })
-                .take_while(move |(row, column)| {
-                    (0..height).contains(row) && (0..width).contains(column)
-                })
+            .flat_map(move |antenna2| {
+                let offset = *antenna2 - *antenna1;
+                iterate(*antenna2, move |position| *position + offset)
+                    .take_while(move |position| input.contains(*position))
             })
     })
 }
@@ -108,13 +97,16 @@ mod tests {
     #[test]
     fn parses_example() {
         assert_eq!(
-            parse(EXAMPLE_INPUT),
+            parse(EXAMPLE_INPUT).unwrap(),
             Input {
                 antennas: [
                     ('A', vec![(5, 6), (8, 8), (9, 9)]),
                     ('0', vec![(1, 8), (2, 5), (3, 7), (4, 4)]),
                 ]
                 .into_iter()
+                .map(|(frequency, antennas)| {
+                    (frequency, antennas.into_iter().map(Point::from).collect())
+                })
                 .collect::<HashMap<_, _>>(),
                 width: 12,
                 height: 12,
@@ -125,18 +117,18 @@ mod tests {
     #[test]
     fn creates_antinodes() {
         assert_eq!(
-            antinodes(&[(3, 4), (5, 5)]).collect::<Vec<_>>(),
-            vec![(7, 6), (1, 3)]
+            antinodes(&[Point::new(3, 4), Point::new(5, 5)]).collect::<Vec<_>>(),
+            vec![Point::new(7, 6), Point::new(1, 3)]
         );
     }
 
     #[test]
     fn solves_part1() {
-        assert_eq!(part1(&parse(EXAMPLE_INPUT)), 14);
+        assert_eq!(part1(&parse(EXAMPLE_INPUT).unwrap()), 14);
     }
 
     #[test]
     fn solves_part2() {
-        assert_eq!(part2(&parse(EXAMPLE_INPUT)), 34);
+        assert_eq!(part2(&parse(EXAMPLE_INPUT).unwrap()), 34);
     }
 }
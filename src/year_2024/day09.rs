@@ -1,6 +1,9 @@
+use crate::ParseError;
 use itertools::Itertools;
 use std::ops::{Add, Range};
 
+crate::solver!(2024, 9, Input);
+
 type Input = Vec<(Option<usize>, u8)>;
 
 pub fn part1(input: &Input) -> usize {
@@ -19,18 +22,29 @@ pub fn part2(input: &Input) -> usize {
     checksum(&blocks(&compact_files(input)))
 }
 
-pub fn parse(input: &str) -> Input {
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let sizes = input
+        .trim_end()
+        .char_indices()
+        .map(|(index, char)| {
+            char.to_digit(10).map(|size| size as u8).ok_or_else(|| {
+                ParseError::at(
+                    input,
+                    &input[index..],
+                    format!("expected a digit, found {:?}", char),
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
     let mut result = Vec::new();
-    for (id, mut chunk) in (&input.bytes().chunks(2)).into_iter().enumerate() {
-        if let Some(size) = chunk.next() {
-            result.push((Some(id), size - b'0'));
-        }
-        if let Some(size) = chunk.next() {
-            result.push((None, size - b'0'));
+    for (id, chunk) in sizes.chunks(2).enumerate() {
+        result.push((Some(id), chunk[0]));
+        if let Some(size) = chunk.get(1) {
+            result.push((None, *size));
         }
     }
 
-    result
+    Ok(result)
 }
 
 fn blocks(input: &Input) -> Vec<Option<usize>> {
@@ -117,7 +131,7 @@ mod tests {
     #[test]
     fn parse_12345() {
         assert_eq!(
-            parse("12345"),
+            parse("12345").unwrap(),
             vec![
                 (Some(0), 1),
                 (None, 2),
@@ -128,31 +142,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parses_trailing_newline() {
+        assert_eq!(parse("12345\n"), parse("12345"));
+        assert_eq!(
+            parse("123x5\n"),
+            Err(ParseError::new(1, 4, "expected a digit, found 'x'"))
+        );
+    }
+
     const EXAMPLE: &str = "2333133121414131402";
 
     #[rstest]
     #[case("12345", "022111222......")]
     #[case(EXAMPLE, "0099811188827773336446555566..............")]
     fn compacts(#[case] input: &str, #[case] expected_output: &str) {
-        let input = parse(input);
+        let input = parse(input).unwrap();
         assert_eq!(print(&compact(&input)), expected_output);
     }
 
     #[test]
     fn solves_example_part1() {
-        assert_eq!(part1(&parse(EXAMPLE)), 1928);
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 1928);
     }
 
     #[test]
     fn compacts_files() {
         assert_eq!(
-            print(&blocks(&compact_files(&parse(EXAMPLE)))),
+            print(&blocks(&compact_files(&parse(EXAMPLE).unwrap()))),
             "00992111777.44.333....5555.6666.....8888.."
         );
     }
 
     #[test]
     fn solves_part2() {
-        assert_eq!(part2(&parse(EXAMPLE)), 2858);
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 2858);
     }
 }
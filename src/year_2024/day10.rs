@@ -1,7 +1,12 @@
-use itertools::Itertools;
-use std::collections::HashMap;
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::render::{Picture, Rgb};
+use crate::search;
+use crate::ParseError;
 
-type Input = HashMap<(isize, isize), u8>;
+crate::solver!(2024, 10, Input, visualise);
+
+type Input = Grid<u8>;
 
 pub fn part1(input: &Input) -> usize {
     trail_heads(input)
@@ -15,70 +20,62 @@ pub fn part2(input: &Input) -> usize {
         .sum()
 }
 
-pub fn parse(input: &str) -> Input {
-    input
-        .lines()
-        .enumerate()
-        .flat_map(|(row, line)| {
-            line.bytes()
-                .enumerate()
-                .map(move |(column, byte)| ((row as isize, column as isize), byte - b'0'))
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    Grid::parse(input, "a height digit", |char| {
+        char.to_digit(10).map(|height| height as u8)
+    })
+}
+
+/// Draws the trails from each trailhead in turn, then all of them together.
+pub fn visualise(input: &Input) -> Vec<Picture> {
+    let map = Picture::new(input, |height| char::from(b'0' + height));
+    let mut all = map.clone();
+    let mut pictures = trail_heads(input)
+        .map(|trail_head| {
+            let trails = search::reachable(trail_head, |position| uphill(input, *position));
+            let summits = trails.iter().filter(|position| input[**position] == 9);
+            all.paint(trails.iter().copied(), Rgb::GREEN)
+                .paint(summits.clone().copied(), Rgb::YELLOW);
+            let mut picture = map.clone();
+            picture
+                .paint(trails.iter().copied(), Rgb::GREEN)
+                .paint(summits.copied(), Rgb::YELLOW)
+                .paint([trail_head], Rgb::RED);
+            picture
         })
-        .collect()
+        .collect::<Vec<_>>();
+    all.paint(trail_heads(input), Rgb::RED);
+    pictures.push(all);
+    pictures
 }
 
-fn trail_heads(input: &Input) -> impl Iterator<Item = (isize, isize)> + use<'_> {
+fn trail_heads(input: &Input) -> impl Iterator<Item = Point> + use<'_> {
     input
         .iter()
         .filter(|(_, height)| **height == 0)
         .map(|(position, _)| position)
-        .cloned()
 }
 
-fn score(input: &Input, position: (isize, isize)) -> usize {
-    let mut positions = vec![position];
-    for height in 1..10 {
-        positions = positions
-            .iter()
-            .flat_map(|position| neighbors(*position))
-            .collect();
-        positions.sort_unstable();
-        positions.dedup();
-        positions.retain_mut(|position| input.get(position).map(|h| *h == height).unwrap_or(false));
-    }
-    positions.len()
+fn score(input: &Input, position: Point) -> usize {
+    search::reachable(position, |position| uphill(input, *position))
+        .into_iter()
+        .filter(|position| input[*position] == 9)
+        .count()
 }
 
-fn rate(input: &Input, position: (isize, isize)) -> usize {
-    let mut paths = vec![vec![position]];
-    for height in 1..10 {
-        paths = paths
-            .iter()
-            .flat_map(|path| {
-                path.last()
-                    .iter()
-                    .flat_map(|position| neighbors(**position))
-                    .filter(|position| input.get(position).map(|h| *h == height).unwrap_or(false))
-                    .map(|position| {
-                        let mut new_path = path.clone();
-                        new_path.push(position);
-                        new_path
-                    })
-                    .collect_vec()
-            })
-            .collect_vec();
-    }
-    paths.len()
+fn rate(input: &Input, position: Point) -> usize {
+    search::count_paths(
+        position,
+        |position| uphill(input, *position),
+        |position| input[*position] == 9,
+    )
 }
 
-fn neighbors((row, column): (isize, isize)) -> impl Iterator<Item = (isize, isize)> {
-    [
-        (row - 1, column),
-        (row + 1, column),
-        (row, column - 1),
-        (row, column + 1),
-    ]
-    .into_iter()
+fn uphill(input: &Input, position: Point) -> impl Iterator<Item = Point> + use<'_> {
+    let height = input[position] + 1;
+    position
+        .neighbours()
+        .filter(move |next| input.get(*next) == Some(&height))
 }
 
 #[cfg(test)]
@@ -97,12 +94,12 @@ mod tests {
 
     #[test]
     fn finds_trailheads() {
-        assert_eq!(9, trail_heads(&(parse(EXAMPLE))).count());
+        assert_eq!(9, trail_heads(&(parse(EXAMPLE).unwrap())).count());
     }
 
     #[test]
     fn scores_trailheads() {
-        let input = parse(EXAMPLE);
+        let input = parse(EXAMPLE).unwrap();
         assert_eq!(
             vec![5, 6, 5, 3, 1, 3, 5, 3, 5],
             trail_heads(&(input))
@@ -114,12 +111,12 @@ mod tests {
 
     #[test]
     fn solves_part1() {
-        assert_eq!(part1(&parse(EXAMPLE)), 36);
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 36);
     }
 
     #[test]
     fn rates_trailheads() {
-        let input = parse(EXAMPLE);
+        let input = parse(EXAMPLE).unwrap();
         assert_eq!(
             vec![20, 24, 10, 4, 1, 4, 5, 8, 5],
             trail_heads(&(input))
@@ -131,6 +128,6 @@ mod tests {
 
     #[test]
     fn solves_part2() {
-        assert_eq!(part2(&parse(EXAMPLE)), 81);
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 81);
     }
 }
@@ -1,6 +1,10 @@
+use crate::parsing::parse_at;
+use crate::ParseError;
 use itertools::{iterate, Itertools};
 use std::collections::HashMap;
 
+crate::solver!(2024, 11, Input);
+
 type Input = HashMap<u64, usize>;
 
 pub fn part1(input: &Input) -> usize {
@@ -19,11 +23,13 @@ pub fn part2(input: &Input) -> usize {
         .sum()
 }
 
-pub fn parse(input: &str) -> Input {
-    input
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    Ok(input
         .split_whitespace()
-        .map(|stone| stone.parse::<u64>().expect("engraved number"))
-        .counts()
+        .map(|stone| parse_at::<u64>(input, stone, "engraved number"))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .counts())
 }
 
 fn blink(input: &Input) -> Input {
@@ -103,6 +109,6 @@ mod tests {
 
     #[test]
     fn solves_part1() {
-        assert_eq!(55312, part1(&parse("125 17")));
+        assert_eq!(55312, part1(&parse("125 17").unwrap()));
     }
 }
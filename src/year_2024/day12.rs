@@ -1,111 +1,88 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::render::Picture;
+use crate::search;
+use crate::ParseError;
+use std::collections::HashSet;
 
-type Input = HashMap<(isize, isize), u8>;
+crate::solver!(2024, 12, Input, visualise);
+
+type Input = Grid<u8>;
 pub fn part1(input: &Input) -> usize {
     total_price(input, price)
 }
 
-fn total_price(input: &Input, region_price: fn(&HashSet<(isize, isize)>) -> usize) -> usize {
+fn total_price(input: &Input, region_price: fn(&HashSet<Point>) -> usize) -> usize {
+    regions(input).iter().map(region_price).sum()
+}
+
+fn regions(input: &Input) -> Vec<HashSet<Point>> {
     let mut visited = HashSet::new();
-    let mut total = 0;
-    for position in input.keys() {
-        if visited.insert(*position) {
+    let mut regions = Vec::new();
+    for position in input.positions() {
+        if visited.insert(position) {
             let region = find_region(input, position);
             visited.extend(&region);
-            total += region_price(&region)
+            regions.push(region);
         }
     }
-    total
+    regions
 }
 
-fn find_region(map: &Input, &position @ (x, y): &(isize, isize)) -> HashSet<(isize, isize)> {
-    let mut region = HashSet::new();
-    let inside = {
-        let plant = *map.get(&position).expect("plant for position");
-        move |x, y| map.get(&(x, y)).map(|p| *p == plant).unwrap_or(false)
-    };
-
-    let mut queue = VecDeque::new();
-    queue.push_back((x, x, y, 1));
-    queue.push_back((x, x, y - 1, -1));
-
-    while let Some((mut x1, x2, y, dy)) = queue.pop_front() {
-        let mut x = x1;
-        if inside(x, y) && !region.contains(&(x, y)) {
-            while inside(x - 1, y) && region.insert((x - 1, y)) {
-                x -= 1;
-            }
-            if x < x1 {
-                queue.push_back((x, x1 - 1, y - dy, -dy));
-            }
-        }
-        while x1 <= x2 {
-            while inside(x1, y) && region.insert((x1, y)) {
-                x1 += 1;
-            }
-            if x1 > x {
-                queue.push_back((x, x1 - 1, y + dy, dy));
-            }
-            if x1 - 1 > x2 {
-                queue.push_back((x2 + 1, x1 - 1, y - dy, -dy));
-            }
-            x1 += 1;
-            while (region.contains(&(x1, y)) || !inside(x1, y)) && x1 < x2 {
-                x1 += 1;
-            }
-            x = x1;
-        }
-    }
-    region
+fn find_region(map: &Input, position: Point) -> HashSet<Point> {
+    let plant = map[position];
+    search::reachable(position, |position| {
+        position
+            .neighbours()
+            .filter(move |next| map.get(*next) == Some(&plant))
+    })
 }
 
-fn price(region: &HashSet<(isize, isize)>) -> usize {
+fn price(region: &HashSet<Point>) -> usize {
     let area = region.len();
     let perimeter = region
         .iter()
-        .flat_map(neighbors)
+        .flat_map(|position| position.neighbours())
         .filter(|position| !region.contains(position))
         .count();
     area * perimeter
 }
 
-fn discounted_price(region: &HashSet<(isize, isize)>) -> usize {
+fn discounted_price(region: &HashSet<Point>) -> usize {
     let area = region.len();
     let sides = region
         .iter()
-        .map(|position| count_corners(region, position))
+        .map(|position| count_corners(region, *position))
         .sum::<usize>();
     area * sides
 }
 
-fn count_corners(region: &HashSet<(isize, isize)>, &(x, y): &(isize, isize)) -> usize {
-    [(-1, -1), (-1, 1), (1, 1), (1, -1)]
-        .iter()
-        .filter(|(dx, dy)| {
-            !region.contains(&(x + *dx, y))
-                && (!region.contains(&(x, y + *dy)) || region.contains(&(x + *dx, y + *dy)))
+fn count_corners(region: &HashSet<Point>, position: Point) -> usize {
+    Point::DIAGONAL
+        .into_iter()
+        .filter(|offset| {
+            !region.contains(&(position + Point::new(offset.row, 0)))
+                && (!region.contains(&(position + Point::new(0, offset.column)))
+                    || region.contains(&(position + *offset)))
         })
         .count()
 }
 
-fn neighbors(&(x, y): &(isize, isize)) -> impl Iterator<Item = (isize, isize)> {
-    [(x - 1, y), (x, y - 1), (x + 1, y), (x, y + 1)].into_iter()
-}
-
 pub fn part2(input: &Input) -> usize {
     total_price(input, discounted_price)
 }
 
-pub fn parse(input: &str) -> Input {
-    input
-        .lines()
-        .enumerate()
-        .flat_map(|(row, line)| {
-            line.bytes()
-                .enumerate()
-                .map(move |(column, plant)| ((row as isize, column as isize), plant))
-        })
-        .collect::<HashMap<_, _>>()
+/// Draws the plots with each region in its own colour.
+pub fn visualise(input: &Input) -> Vec<Picture> {
+    let mut picture = Picture::new(input, |plant| *plant as char);
+    picture.paint_regions(regions(input));
+    vec![picture]
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    Grid::parse(input, "a plant letter", |char| {
+        char.is_ascii_uppercase().then_some(char as u8)
+    })
 }
 
 #[cfg(test)]
@@ -134,7 +111,7 @@ MMMISSJEEE";
     #[case(EXAMPLE1, 772)]
     #[case(EXAMPLE2, 1930)]
     fn solves_part1(#[case] input: &str, #[case] price: usize) {
-        assert_eq!(part1(&parse(input)), price);
+        assert_eq!(part1(&parse(input).unwrap()), price);
     }
 
     #[rstest]
@@ -167,7 +144,7 @@ AAAAAA
     )]
     #[case(EXAMPLE2, 1206)]
     fn solves_part2(#[case] input: &str, #[case] price: usize) {
-        assert_eq!(part2(&parse(input)), price);
+        assert_eq!(part2(&parse(input).unwrap()), price);
     }
 
     #[rstest]
@@ -177,7 +154,7 @@ AAAAAA
         #[case] position: (isize, isize),
         #[case] corners: usize,
     ) {
-        let region: HashSet<(isize, isize)> = region.iter().cloned().collect();
-        assert_eq!(count_corners(&region, &position), corners);
+        let region: HashSet<Point> = region.iter().cloned().map(Point::from).collect();
+        assert_eq!(count_corners(&region, position.into()), corners);
     }
 }
@@ -0,0 +1,139 @@
+use crate::parsing::{parse_at, split_once_at};
+use crate::ParseError;
+
+crate::solver!(2024, 13, Vec<Machine>);
+
+/// How far the prizes really are, in part two.
+const CORRECTION: i64 = 10_000_000_000_000;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Machine {
+    a: (i64, i64),
+    b: (i64, i64),
+    prize: (i64, i64),
+}
+
+impl Machine {
+    /// Tokens to win the prize, at three for a press of A and one for a press of B.
+    ///
+    /// The presses solve two linear equations, which by Cramer's rule have at most one solution
+    /// unless the buttons move the claw in the same direction, which no machine does.
+    fn tokens(&self) -> Option<i64> {
+        let determinant = self.a.0 * self.b.1 - self.a.1 * self.b.0;
+        if determinant == 0 {
+            return None;
+        }
+        let a = self.prize.0 * self.b.1 - self.prize.1 * self.b.0;
+        let b = self.a.0 * self.prize.1 - self.a.1 * self.prize.0;
+        if a % determinant != 0 || b % determinant != 0 {
+            return None;
+        }
+        let (a, b) = (a / determinant, b / determinant);
+        (a >= 0 && b >= 0).then_some(3 * a + b)
+    }
+
+    fn corrected(&self) -> Machine {
+        Machine {
+            prize: (self.prize.0 + CORRECTION, self.prize.1 + CORRECTION),
+            ..*self
+        }
+    }
+}
+
+pub fn part1(machines: &[Machine]) -> i64 {
+    machines.iter().filter_map(Machine::tokens).sum()
+}
+
+pub fn part2(machines: &[Machine]) -> i64 {
+    machines
+        .iter()
+        .filter_map(|machine| machine.corrected().tokens())
+        .sum()
+}
+
+pub fn parse(input: &str) -> Result<Vec<Machine>, ParseError> {
+    input
+        .split("\n\n")
+        .map(|block| {
+            let mut lines = block.lines();
+            let mut line = |label: &str, sign: &str| {
+                let line = lines
+                    .next()
+                    .ok_or_else(|| ParseError::at(input, block, format!("expected {:?}", label)))?;
+                let (_, offsets) = split_once_at(input, line, &format!("{}: ", label))?;
+                let (x, y) = split_once_at(input, offsets, ", ")?;
+                let coordinate = |axis: &str, name: &str| {
+                    let (_, value) = split_once_at(input, axis, &format!("{}{}", name, sign))?;
+                    parse_at(input, value, "a number")
+                };
+                Ok::<_, ParseError>((coordinate(x, "X")?, coordinate(y, "Y")?))
+            };
+            Ok(Machine {
+                a: line("Button A", "+")?,
+                b: line("Button B", "+")?,
+                prize: line("Prize", "=")?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+";
+
+    #[rstest]
+    #[case(0, Some(280))]
+    #[case(1, None)]
+    #[case(2, Some(200))]
+    #[case(3, None)]
+    fn counts_tokens(#[case] machine: usize, #[case] tokens: Option<i64>) {
+        assert_eq!(parse(EXAMPLE).unwrap()[machine].tokens(), tokens);
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 480);
+    }
+
+    #[test]
+    fn wins_other_prizes_after_correction() {
+        let winnable = parse(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(|machine| machine.corrected().tokens().is_some())
+            .collect::<Vec<_>>();
+        assert_eq!(winnable, [false, true, false, true]);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 875318608908);
+    }
+
+    #[test]
+    fn reports_missing_line() {
+        assert_eq!(
+            parse("Button A: X+94, Y+34\nButton B: X+22, Y+67\n"),
+            Err(ParseError::new(1, 1, "expected \"Prize\""))
+        );
+    }
+}
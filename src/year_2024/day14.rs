@@ -0,0 +1,187 @@
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::parsing::{parse_at, split_once_at};
+use crate::render::{Picture, Rgb};
+use crate::ParseError;
+
+crate::solver!(2024, 14, Vec<Robot>, visualise);
+
+/// Height and width of the bathroom.
+const SPACE: Point = Point::new(103, 101);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Robot {
+    position: Point,
+    velocity: Point,
+}
+
+impl Robot {
+    /// Where the robot is after `seconds`, teleporting to the other side at the edges.
+    fn after(&self, seconds: isize, space: Point) -> Point {
+        let position = self.position + self.velocity * seconds;
+        Point::new(
+            position.row.rem_euclid(space.row),
+            position.column.rem_euclid(space.column),
+        )
+    }
+}
+
+pub fn part1(robots: &[Robot]) -> usize {
+    safety_factor(robots, 100, SPACE)
+}
+
+pub fn part2(robots: &[Robot]) -> isize {
+    easter_egg(robots, SPACE)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Robot>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (position, velocity) = split_once_at(input, line, " ")?;
+            let point = |fragment: &str, prefix: &str| {
+                let Some(coordinates) = fragment.strip_prefix(prefix) else {
+                    return Err(ParseError::at(
+                        input,
+                        fragment,
+                        format!("expected {:?}", prefix),
+                    ));
+                };
+                let (x, y) = split_once_at(input, coordinates, ",")?;
+                Ok(Point::new(
+                    parse_at(input, y, "a coordinate")?,
+                    parse_at(input, x, "a coordinate")?,
+                ))
+            };
+            Ok(Robot {
+                position: point(position, "p=")?,
+                velocity: point(velocity, "v=")?,
+            })
+        })
+        .collect()
+}
+
+/// Draws the robots when they show the Christmas tree.
+pub fn visualise(robots: &[Robot]) -> Vec<Picture> {
+    let seconds = easter_egg(robots, SPACE);
+    let space = Grid::new(SPACE.column as usize, SPACE.row as usize, ' ');
+    let mut picture = Picture::new(&space, |tile| *tile);
+    picture.paint(
+        robots.iter().map(|robot| robot.after(seconds, SPACE)),
+        Rgb::GREEN,
+    );
+    vec![picture]
+}
+
+/// Product of the numbers of robots in each quadrant, ignoring those in the middle.
+fn safety_factor(robots: &[Robot], seconds: isize, space: Point) -> usize {
+    let middle = Point::new(space.row / 2, space.column / 2);
+    let mut quadrants = [0; 4];
+    for position in robots.iter().map(|robot| robot.after(seconds, space)) {
+        if position.row != middle.row && position.column != middle.column {
+            let index = 2 * usize::from(position.row > middle.row)
+                + usize::from(position.column > middle.column);
+            quadrants[index] += 1;
+        }
+    }
+    quadrants.iter().product()
+}
+
+/// First second when most robots arrange themselves into a picture of a Christmas tree.
+///
+/// The robots huddle together in both directions at once, so their columns vary the least at
+/// that second modulo the width and their rows at that second modulo the height. Both repeat
+/// independently, and the Chinese remainder theorem puts them together.
+fn easter_egg(robots: &[Robot], space: Point) -> isize {
+    let huddled = |period: isize, coordinate: fn(Point) -> isize| {
+        (0..period)
+            .min_by_key(|seconds| {
+                let values = robots
+                    .iter()
+                    .map(|robot| coordinate(robot.after(*seconds, space)))
+                    .collect::<Vec<_>>();
+                let mean = values.iter().sum::<isize>() / values.len().max(1) as isize;
+                values
+                    .iter()
+                    .map(|value| (value - mean).pow(2))
+                    .sum::<isize>()
+            })
+            .unwrap_or(0)
+    };
+    let column = huddled(space.column, |point| point.column);
+    let row = huddled(space.row, |point| point.row);
+    (0..space.row)
+        .map(|cycles| column + cycles * space.column)
+        .find(|seconds| seconds % space.row == row)
+        .expect("a width and height without common factors")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+";
+
+    const EXAMPLE_SPACE: Point = Point::new(7, 11);
+
+    #[test]
+    fn teleports_at_edges() {
+        let robot = parse("p=2,4 v=2,-3").unwrap()[0];
+        let positions = (0..=5)
+            .map(|seconds| robot.after(seconds, EXAMPLE_SPACE))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            [(4, 2), (1, 4), (5, 6), (2, 8), (6, 10), (3, 1)].map(Point::from)
+        );
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(
+            safety_factor(&parse(EXAMPLE).unwrap(), 100, EXAMPLE_SPACE),
+            12
+        );
+    }
+
+    #[test]
+    fn finds_huddled_robots() {
+        // Robots that all meet in the middle after 1234 seconds, coming from everywhere.
+        let meeting = Point::new(51, 50);
+        let robots = (0..50)
+            .map(|index| {
+                let velocity = Point::new(index % 7 - 3, index % 5 + 1);
+                Robot {
+                    position: Robot {
+                        position: meeting,
+                        velocity: -velocity,
+                    }
+                    .after(1234, SPACE),
+                    velocity,
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(easter_egg(&robots, SPACE), 1234);
+    }
+
+    #[test]
+    fn reports_invalid_robot() {
+        assert_eq!(
+            parse("p=0,4 x=3,-3"),
+            Err(ParseError::new(1, 7, "expected \"v=\""))
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use crate::geometry::Direction::{self, *};
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::ParseError;
+use std::collections::HashSet;
+
+crate::solver!(2024, 15, Input);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Tile {
+    Empty,
+    Wall,
+    Box,
+    BoxLeft,
+    BoxRight,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+    warehouse: Grid<Tile>,
+    robot: Point,
+    moves: Vec<Direction>,
+}
+
+impl Input {
+    /// The warehouse twice as wide, with every box taking up two tiles.
+    fn widened(&self) -> Input {
+        let mut warehouse = Grid::new(
+            2 * self.warehouse.width(),
+            self.warehouse.height(),
+            Tile::Empty,
+        );
+        for (position, tile) in self.warehouse.iter() {
+            let left = Point::new(position.row, 2 * position.column);
+            let right = left + Right;
+            (warehouse[left], warehouse[right]) = match tile {
+                Tile::Box => (Tile::BoxLeft, Tile::BoxRight),
+                tile => (*tile, *tile),
+            };
+        }
+        Input {
+            warehouse,
+            robot: Point::new(self.robot.row, 2 * self.robot.column),
+            moves: self.moves.clone(),
+        }
+    }
+
+    /// The warehouse after the robot has made all its moves.
+    fn run(&self) -> Grid<Tile> {
+        let mut warehouse = self.warehouse.clone();
+        let mut robot = self.robot;
+        for direction in &self.moves {
+            if push(&mut warehouse, robot, *direction) {
+                robot = robot + *direction;
+            }
+        }
+        warehouse
+    }
+}
+
+pub fn part1(input: &Input) -> isize {
+    gps_sum(&input.run())
+}
+
+pub fn part2(input: &Input) -> isize {
+    gps_sum(&input.widened().run())
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let (map, moves) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::end(input, "expected the moves after an empty line"))?;
+    let map = Grid::parse(map, "a wall, box, robot or floor", |char| {
+        "#.O@".contains(char).then_some(char)
+    })?;
+    let robot = map
+        .find(|tile| *tile == '@')
+        .ok_or_else(|| ParseError::new(1, 1, "expected a robot"))?;
+    let warehouse = map.map(|tile| match tile {
+        '#' => Tile::Wall,
+        'O' => Tile::Box,
+        _ => Tile::Empty,
+    });
+    if warehouse.positions().any(|position| {
+        warehouse[position] != Tile::Wall
+            && position.neighbours().any(|next| !warehouse.contains(next))
+    }) {
+        return Err(ParseError::end(
+            input,
+            "expected a warehouse enclosed by walls",
+        ));
+    }
+    let moves = moves
+        .char_indices()
+        .filter(|(_, char)| *char != '\n')
+        .map(|(index, char)| match char {
+            '^' => Ok(Up),
+            '>' => Ok(Right),
+            'v' => Ok(Down),
+            '<' => Ok(Left),
+            _ => Err(ParseError::at(
+                input,
+                &moves[index..],
+                format!("expected a move, found {:?}", char),
+            )),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Input {
+        warehouse,
+        robot,
+        moves,
+    })
+}
+
+/// Moves the robot and all the boxes in front of it one step, if none of them is blocked by a
+/// wall. Returns whether they moved.
+fn push(warehouse: &mut Grid<Tile>, robot: Point, direction: Direction) -> bool {
+    // Collects the tiles that move, each after the one pushing it.
+    let mut moving = vec![robot];
+    let mut seen = HashSet::from([robot]);
+    let mut index = 0;
+    while let Some(&position) = moving.get(index) {
+        let ahead = position + direction;
+        let pushed = match warehouse[ahead] {
+            Tile::Empty => vec![],
+            Tile::Wall => return false,
+            Tile::Box => vec![ahead],
+            Tile::BoxLeft => vec![ahead, ahead + Right],
+            Tile::BoxRight => vec![ahead, ahead + Left],
+        };
+        for position in pushed {
+            if seen.insert(position) {
+                moving.push(position);
+            }
+        }
+        index += 1;
+    }
+    // The robot is not on the map, so only the boxes move there.
+    for position in moving.into_iter().skip(1).rev() {
+        warehouse[position + direction] = warehouse[position];
+        warehouse[position] = Tile::Empty;
+    }
+    true
+}
+
+/// Sum of the GPS coordinates of the boxes, measured to their left edge.
+fn gps_sum(warehouse: &Grid<Tile>) -> isize {
+    warehouse
+        .iter()
+        .filter(|(_, tile)| matches!(tile, Tile::Box | Tile::BoxLeft))
+        .map(|(position, _)| 100 * position.row + position.column)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const SMALL_EXAMPLE: &str = "\
+########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<
+";
+
+    const WIDE_EXAMPLE: &str = "\
+#######
+#...#.#
+#.....#
+#..OO@#
+#..O..#
+#.....#
+#######
+
+<vv<<^^<<^^
+";
+
+    #[rstest]
+    #[case(
+        SMALL_EXAMPLE,
+        false,
+        "\
+########
+#....OO#
+##.....#
+#.....O#
+#.#O@..#
+#...O..#
+#...O..#
+########"
+    )]
+    #[case(
+        WIDE_EXAMPLE,
+        true,
+        "\
+##############
+##...[].##..##
+##...@.[]...##
+##....[]....##
+##..........##
+##..........##
+##############"
+    )]
+    fn moves_boxes(#[case] input: &str, #[case] wide: bool, #[case] expected: &str) {
+        let input = parse(input).unwrap();
+        let input = if wide { input.widened() } else { input };
+        let expected = Grid::parse(expected, "a tile", |char| match char {
+            '#' => Some(Tile::Wall),
+            'O' => Some(Tile::Box),
+            '[' => Some(Tile::BoxLeft),
+            ']' => Some(Tile::BoxRight),
+            _ => Some(Tile::Empty),
+        })
+        .unwrap();
+        assert_eq!(input.run(), expected);
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(part1(&parse(SMALL_EXAMPLE).unwrap()), 2028);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(part2(&parse(WIDE_EXAMPLE).unwrap()), 105 + 207 + 306);
+    }
+
+    #[test]
+    fn stops_at_walls() {
+        let input = parse("#####\n#@OO#\n#####\n\n>>").unwrap();
+        assert_eq!(input.run(), input.warehouse);
+    }
+
+    #[test]
+    fn requires_walls_around() {
+        assert_eq!(
+            parse("#####\n#@O..\n#####\n\n>>>"),
+            Err(ParseError::new(
+                5,
+                4,
+                "expected a warehouse enclosed by walls"
+            ))
+        );
+    }
+
+    #[test]
+    fn reports_invalid_move() {
+        assert_eq!(
+            parse("#####\n#@.O#\n#####\n\n>>\n>x"),
+            Err(ParseError::new(6, 2, "expected a move, found 'x'"))
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use crate::geometry::Direction::{self, *};
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::search;
+use crate::ParseError;
+use std::collections::{HashMap, HashSet};
+
+crate::solver!(2024, 16, Input);
+
+const STEP: u32 = 1;
+const TURN: u32 = 1000;
+
+type State = (Point, Direction);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+    walls: Grid<bool>,
+    start: Point,
+    end: Point,
+}
+
+impl Input {
+    fn moves(&self, (position, direction): State) -> impl Iterator<Item = (State, u32)> + '_ {
+        let ahead = position + direction;
+        (!self.walls[ahead])
+            .then_some(((ahead, direction), STEP))
+            .into_iter()
+            .chain([
+                ((position, direction.turn_left()), TURN),
+                ((position, direction.turn_right()), TURN),
+            ])
+    }
+
+    /// The moves that lead to a state, the reverse of [`Input::moves`].
+    fn reverse_moves(&self, (position, direction): State) -> impl Iterator<Item = (State, u32)> {
+        let behind = position + direction.opposite();
+        (!self.walls[behind])
+            .then_some(((behind, direction), STEP))
+            .into_iter()
+            .chain([
+                ((position, direction.turn_left()), TURN),
+                ((position, direction.turn_right()), TURN),
+            ])
+    }
+
+    /// Lowest score of every state the reindeer can reach, starting east.
+    fn scores(&self) -> HashMap<State, u32> {
+        search::costs((self.start, Right), |state| self.moves(*state))
+    }
+
+    /// The end states with the lowest score, and that score.
+    fn best_ends(&self, scores: &HashMap<State, u32>) -> (Vec<State>, u32) {
+        let ends = Direction::ALL
+            .into_iter()
+            .filter_map(|direction| {
+                Some(((self.end, direction), *scores.get(&(self.end, direction))?))
+            })
+            .collect::<Vec<_>>();
+        let best = ends.iter().map(|(_, score)| *score).min().unwrap_or(0);
+        let best_ends = ends
+            .into_iter()
+            .filter(|(_, score)| *score == best)
+            .map(|(state, _)| state)
+            .collect();
+        (best_ends, best)
+    }
+}
+
+pub fn part1(input: &Input) -> u32 {
+    input.best_ends(&input.scores()).1
+}
+
+/// Counts the tiles on any of the best paths, by walking back from the end along all moves that
+/// account exactly for the difference in score.
+pub fn part2(input: &Input) -> usize {
+    let scores = input.scores();
+    let (ends, _) = input.best_ends(&scores);
+    let mut seen = ends.iter().copied().collect::<HashSet<_>>();
+    let mut stack = ends;
+    while let Some(state) = stack.pop() {
+        for (previous, cost) in input.reverse_moves(state) {
+            if scores.get(&previous).map(|score| score + cost) == Some(scores[&state])
+                && seen.insert(previous)
+            {
+                stack.push(previous);
+            }
+        }
+    }
+    seen.into_iter()
+        .map(|(position, _)| position)
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let maze = Grid::parse(input, "a wall, floor, start or end", |char| {
+        "#.SE".contains(char).then_some(char)
+    })?;
+    let find = |tile: char, what: &str| {
+        maze.find(|char| *char == tile)
+            .ok_or_else(|| ParseError::end(input, format!("expected the {}", what)))
+    };
+    let (start, end) = (find('S', "start")?, find('E', "end")?);
+    let walls = maze.map(|tile| *tile == '#');
+    if walls
+        .positions()
+        .any(|position| !walls[position] && position.neighbours().any(|next| !walls.contains(next)))
+    {
+        return Err(ParseError::end(input, "expected a maze enclosed by walls"));
+    }
+    Ok(Input { walls, start, end })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+";
+
+    const SECOND_EXAMPLE: &str = "\
+#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################
+";
+
+    #[rstest]
+    #[case(EXAMPLE, 7036)]
+    #[case(SECOND_EXAMPLE, 11048)]
+    fn solves_part1(#[case] input: &str, #[case] score: u32) {
+        assert_eq!(part1(&parse(input).unwrap()), score);
+    }
+
+    #[rstest]
+    #[case(EXAMPLE, 45)]
+    #[case(SECOND_EXAMPLE, 64)]
+    fn solves_part2(#[case] input: &str, #[case] tiles: usize) {
+        assert_eq!(part2(&parse(input).unwrap()), tiles);
+    }
+
+    #[test]
+    fn requires_walls_around() {
+        assert_eq!(
+            parse("#####\n#S.E.\n#####\n"),
+            Err(ParseError::new(4, 1, "expected a maze enclosed by walls"))
+        );
+    }
+}
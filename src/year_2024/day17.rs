@@ -0,0 +1,364 @@
+use crate::parsing::{parse_at, split_once_at};
+use crate::vm::{self, Flow, Machine};
+use crate::ParseError;
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+
+crate::solver!(2024, 17, Input);
+
+const A: usize = 0;
+const B: usize = 1;
+const C: usize = 2;
+/// Steps a candidate for part two may run, far more than any program that outputs itself needs.
+const LIMIT: usize = 10_000;
+
+/// An operand that is either its own value or, from 4 on, the value of a register.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Combo {
+    Literal(u64),
+    Register(usize),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Instruction {
+    /// Divides A by a power of two into A.
+    Adv(Combo),
+    /// XORs B with a literal.
+    Bxl(u64),
+    /// Sets B to the lowest three bits of the operand.
+    Bst(Combo),
+    /// Jumps to an instruction unless A is zero.
+    Jnz(usize),
+    /// XORs B with C.
+    Bxc,
+    /// Outputs the lowest three bits of the operand.
+    Out(Combo),
+    /// Divides A by a power of two into B.
+    Bdv(Combo),
+    /// Divides A by a power of two into C.
+    Cdv(Combo),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+    registers: [u64; 3],
+    /// The program as numbers, which is what part two makes it output.
+    program: Vec<u8>,
+    instructions: Vec<Instruction>,
+}
+
+impl Combo {
+    fn value(self, registers: &[u64]) -> u64 {
+        match self {
+            Combo::Literal(value) => value,
+            Combo::Register(register) => registers[register],
+        }
+    }
+}
+
+impl vm::Instruction for Instruction {
+    type Value = u64;
+
+    fn execute(&self, registers: &mut [u64], output: &mut Vec<u64>) -> Flow {
+        match *self {
+            Instruction::Adv(operand) => {
+                registers[A] = divide(registers[A], operand.value(registers))
+            }
+            Instruction::Bxl(literal) => registers[B] ^= literal,
+            Instruction::Bst(operand) => registers[B] = operand.value(registers) % 8,
+            Instruction::Jnz(target) if registers[A] != 0 => return Flow::Goto(target),
+            Instruction::Jnz(_) => {}
+            Instruction::Bxc => registers[B] ^= registers[C],
+            Instruction::Out(operand) => output.push(operand.value(registers) % 8),
+            Instruction::Bdv(operand) => {
+                registers[B] = divide(registers[A], operand.value(registers))
+            }
+            Instruction::Cdv(operand) => {
+                registers[C] = divide(registers[A], operand.value(registers))
+            }
+        }
+        Flow::Next
+    }
+
+    fn branch(&self) -> Option<Flow> {
+        match *self {
+            Instruction::Jnz(target) => Some(Flow::Goto(target)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Combo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Combo::Literal(value) => write!(f, "{}", value),
+            Combo::Register(register) => write!(f, "{}", ["A", "B", "C"][register]),
+        }
+    }
+}
+
+/// The instruction in assembly, with jump targets as instruction indexes rather than positions
+/// in the program.
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Adv(operand) => write!(f, "adv {}", operand),
+            Instruction::Bxl(literal) => write!(f, "bxl {}", literal),
+            Instruction::Bst(operand) => write!(f, "bst {}", operand),
+            Instruction::Jnz(target) => write!(f, "jnz {}", target),
+            Instruction::Bxc => write!(f, "bxc"),
+            Instruction::Out(operand) => write!(f, "out {}", operand),
+            Instruction::Bdv(operand) => write!(f, "bdv {}", operand),
+            Instruction::Cdv(operand) => write!(f, "cdv {}", operand),
+        }
+    }
+}
+
+pub fn part1(input: &Input) -> String {
+    run(&input.instructions, input.registers).0.iter().join(",")
+}
+
+pub fn part2(input: &Input) -> u64 {
+    quine(input).expect("a value of A that makes the program output itself")
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let mut lines = input.lines();
+    let mut registers = [0; 3];
+    for (register, name) in registers.iter_mut().zip(["A", "B", "C"]) {
+        let line = lines
+            .next()
+            .ok_or_else(|| ParseError::end(input, format!("expected register {}", name)))?;
+        let (_, value) = split_once_at(input, line, &format!("Register {}: ", name))?;
+        *register = parse_at(input, value, "a register value")?;
+    }
+    let line = lines
+        .find(|line| !line.is_empty())
+        .ok_or_else(|| ParseError::end(input, "expected the program"))?;
+    let (_, program) = split_once_at(input, line, "Program: ")?;
+    let program = program
+        .split(',')
+        .map(|value| match parse_at(input, value, "a 3-bit number")? {
+            number @ 0..8 => Ok((value, number)),
+            _ => Err(ParseError::at(
+                input,
+                value,
+                format!("expected a 3-bit number, found {:?}", value),
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let instructions = program
+        .chunks(2)
+        .map(|pair| match *pair {
+            [(_, opcode), (operand, value)] => {
+                decode(opcode, value).map_err(|message| ParseError::at(input, operand, message))
+            }
+            [(opcode, _)] => Err(ParseError::at(input, opcode, "expected an operand")),
+            _ => unreachable!("pairs of numbers"),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Input {
+        registers,
+        program: program.into_iter().map(|(_, number)| number).collect(),
+        instructions,
+    })
+}
+
+/// The instruction with an opcode and operand, or what is wrong with the operand.
+fn decode(opcode: u8, operand: u8) -> Result<Instruction, &'static str> {
+    let literal = u64::from(operand);
+    let combo = match operand {
+        0..=3 => Ok(Combo::Literal(literal)),
+        4..=6 => Ok(Combo::Register(usize::from(operand - 4))),
+        _ => Err("expected a combo operand, found the reserved 7"),
+    };
+    Ok(match opcode {
+        0 => Instruction::Adv(combo?),
+        1 => Instruction::Bxl(literal),
+        2 => Instruction::Bst(combo?),
+        // Jumps land on opcodes, which are at even positions.
+        3 if operand.is_multiple_of(2) => Instruction::Jnz(usize::from(operand / 2)),
+        3 => return Err("expected an even jump target"),
+        4 => Instruction::Bxc,
+        5 => Instruction::Out(combo?),
+        6 => Instruction::Bdv(combo?),
+        _ => Instruction::Cdv(combo?),
+    })
+}
+
+/// Runs the program until it halts, returning its output and the final registers.
+fn run(instructions: &[Instruction], registers: [u64; 3]) -> (Vec<u8>, [u64; 3]) {
+    let mut machine = Machine::new(instructions, registers);
+    machine.run();
+    let output = machine.output().iter().map(|value| *value as u8).collect();
+    let registers = machine
+        .registers()
+        .try_into()
+        .expect("registers A, B and C");
+    (output, registers)
+}
+
+/// `numerator / 2^exponent`, which is zero once all bits are shifted out.
+fn divide(numerator: u64, exponent: u64) -> u64 {
+    u32::try_from(exponent)
+        .ok()
+        .and_then(|exponent| numerator.checked_shr(exponent))
+        .unwrap_or(0)
+}
+
+/// Bits the program shifts out of A in every round, if it has the shape part two relies on: a
+/// single loop over the whole program, which divides A by a constant power of two once.
+fn shift_per_round(instructions: &[Instruction]) -> Option<u32> {
+    let whole = vm::Loop {
+        start: 0,
+        end: instructions.len().checked_sub(1)?,
+    };
+    if vm::loops(instructions) != [whole] {
+        return None;
+    }
+    let shifts = instructions
+        .iter()
+        .filter(|instruction| matches!(instruction, Instruction::Adv(_)))
+        .collect::<Vec<_>>();
+    match shifts[..] {
+        [Instruction::Adv(Combo::Literal(bits))] if *bits > 0 => u32::try_from(*bits).ok(),
+        _ => None,
+    }
+}
+
+/// Lowest value of register A that makes the program output a copy of itself.
+///
+/// The program is checked to consume A a few bits per round of a single loop, so that it can be
+/// solved backwards from its last output with [`vm::solve_backwards`].
+fn quine(input: &Input) -> Option<u64> {
+    let bits = shift_per_round(&input.instructions)?;
+    let program = input
+        .program
+        .iter()
+        .map(|number| u64::from(*number))
+        .collect::<Vec<_>>();
+    vm::solve_backwards(
+        &input.instructions,
+        &input.registers,
+        A,
+        bits,
+        &program,
+        LIMIT,
+    )
+    .first()
+    .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "\
+Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0
+";
+
+    const QUINE: &str = "\
+Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0
+";
+
+    #[rstest]
+    #[case([0, 0, 9], &[2, 6], &[], [0, 1, 9])]
+    #[case([10, 0, 0], &[5, 0, 5, 1, 5, 4], &[0, 1, 2], [10, 0, 0])]
+    #[case(
+        [2024, 0, 0],
+        &[0, 1, 5, 4, 3, 0],
+        &[4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0],
+        [0, 0, 0]
+    )]
+    #[case([0, 29, 0], &[1, 7], &[], [0, 26, 0])]
+    #[case([0, 2024, 43690], &[4, 0], &[], [0, 44354, 43690])]
+    fn runs_programs(
+        #[case] registers: [u64; 3],
+        #[case] program: &[u8],
+        #[case] output: &[u8],
+        #[case] after: [u64; 3],
+    ) {
+        assert_eq!(
+            run(&decode_all(program), registers),
+            (output.to_vec(), after)
+        );
+    }
+
+    fn decode_all(program: &[u8]) -> Vec<Instruction> {
+        program
+            .chunks(2)
+            .map(|pair| decode(pair[0], pair[1]).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn shifts_out_all_bits() {
+        assert_eq!(
+            run(&decode_all(&[6, 5, 7, 4]), [u64::MAX, 64, 1000]).1,
+            [u64::MAX, 0, 0]
+        );
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), "4,6,3,5,6,3,5,2,1,0");
+    }
+
+    #[test]
+    fn disassembles_programs() {
+        assert_eq!(
+            vm::disassemble(&parse(QUINE).unwrap().instructions),
+            "\
+┌ 0  adv 3  from 2
+│ 1  out A
+└ 2  jnz 0  loops back to 0
+"
+        );
+    }
+
+    #[rstest]
+    #[case(&[0, 3, 5, 4, 3, 0], Some(3))]
+    #[case(&[2, 4, 1, 2, 7, 5, 0, 2, 5, 5, 3, 0], Some(2))]
+    #[case(&[0, 3, 5, 4], None)]
+    #[case(&[0, 3, 5, 4, 3, 2], None)]
+    #[case(&[0, 3, 0, 1, 5, 4, 3, 0], None)]
+    #[case(&[0, 4, 5, 4, 3, 0], None)]
+    fn checks_shape(#[case] program: &[u8], #[case] bits: Option<u32>) {
+        assert_eq!(shift_per_round(&decode_all(program)), bits);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(part2(&parse(QUINE).unwrap()), 117440);
+    }
+
+    #[rstest]
+    #[case(
+        "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,7\n",
+        ParseError::new(5, 12, "expected a combo operand, found the reserved 7")
+    )]
+    #[case(
+        "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,9\n",
+        ParseError::new(5, 12, "expected a 3-bit number, found \"9\"")
+    )]
+    #[case(
+        "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,2,3\n",
+        ParseError::new(5, 14, "expected an operand")
+    )]
+    #[case(
+        "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 5,4,3,1\n",
+        ParseError::new(5, 16, "expected an even jump target")
+    )]
+    fn rejects_invalid_programs(#[case] input: &str, #[case] error: ParseError) {
+        assert_eq!(parse(input), Err(error));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::parsing::{parse_at, split_once_at};
+use crate::search;
+use crate::ParseError;
+
+crate::solver!(2024, 18, Vec<Point>);
+
+/// Width and height of the memory space.
+const SIZE: usize = 71;
+/// Bytes that have fallen when part one starts.
+const FALLEN: usize = 1024;
+
+pub fn part1(bytes: &[Point]) -> usize {
+    steps(bytes, SIZE, FALLEN).expect("a way to the exit")
+}
+
+pub fn part2(bytes: &[Point]) -> String {
+    let byte = first_blocking(bytes, SIZE).expect("a byte that cuts off the exit");
+    format!("{},{}", byte.column, byte.row)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Point>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (x, y) = split_once_at(input, line, ",")?;
+            let coordinate = |value| match parse_at(input, value, "a coordinate")? {
+                coordinate @ 0..=70 => Ok(coordinate),
+                _ => Err(ParseError::at(
+                    input,
+                    value,
+                    format!("expected a coordinate up to 70, found {:?}", value),
+                )),
+            };
+            Ok(Point::new(coordinate(y)?, coordinate(x)?))
+        })
+        .collect()
+}
+
+/// Fewest steps from the top left to the bottom right corner once `fallen` bytes have fallen.
+fn steps(bytes: &[Point], size: usize, fallen: usize) -> Option<usize> {
+    let mut corrupted = Grid::new(size, size, false);
+    for byte in &bytes[..fallen.min(bytes.len())] {
+        if let Some(cell) = corrupted.get_mut(*byte) {
+            *cell = true;
+        }
+    }
+    let exit = Point::new(size as isize - 1, size as isize - 1);
+    let path = search::bfs(
+        Point::ORIGIN,
+        |position| {
+            position
+                .neighbours()
+                .filter(|next| corrupted.get(*next) == Some(&false))
+                .collect::<Vec<_>>()
+        },
+        |position| *position == exit,
+    )?;
+    Some(path.len() - 1)
+}
+
+/// The first byte after which the exit cannot be reached, found by bisecting the number of fallen
+/// bytes.
+fn first_blocking(bytes: &[Point], size: usize) -> Option<Point> {
+    let counts = (1..=bytes.len()).collect::<Vec<_>>();
+    let reachable = counts.partition_point(|fallen| steps(bytes, size, *fallen).is_some());
+    bytes.get(reachable).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0
+";
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(steps(&parse(EXAMPLE).unwrap(), 7, 12), Some(22));
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(
+            first_blocking(&parse(EXAMPLE).unwrap(), 7),
+            Some(Point::new(1, 6))
+        );
+    }
+
+    #[test]
+    fn rejects_bytes_outside_memory() {
+        assert_eq!(
+            parse("5,4\n71,3\n"),
+            Err(ParseError::new(
+                2,
+                1,
+                "expected a coordinate up to 70, found \"71\""
+            ))
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::ParseError;
+
+crate::solver!(2024, 19, Input);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+    towels: Vec<String>,
+    designs: Vec<String>,
+}
+
+impl Input {
+    /// Number of ways to arrange towels into the design, counted for every suffix of the design
+    /// from the shortest on.
+    fn arrangements(&self, design: &str) -> u64 {
+        let mut ways = vec![0; design.len() + 1];
+        ways[design.len()] = 1;
+        for start in (0..design.len()).rev() {
+            ways[start] = self
+                .towels
+                .iter()
+                .filter(|towel| design[start..].starts_with(towel.as_str()))
+                .map(|towel| ways[start + towel.len()])
+                .sum();
+        }
+        ways[0]
+    }
+}
+
+pub fn part1(input: &Input) -> usize {
+    input
+        .designs
+        .iter()
+        .filter(|design| input.arrangements(design) > 0)
+        .count()
+}
+
+pub fn part2(input: &Input) -> u64 {
+    input
+        .designs
+        .iter()
+        .map(|design| input.arrangements(design))
+        .sum()
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let (towels, designs) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::end(input, "expected the designs after an empty line"))?;
+    let stripes = |pattern: &str| match pattern
+        .char_indices()
+        .find(|(_, char)| !"wubrg".contains(*char))
+    {
+        Some((index, char)) => Err(ParseError::at(
+            input,
+            &pattern[index..],
+            format!("expected a stripe colour, found {:?}", char),
+        )),
+        None if pattern.is_empty() => Err(ParseError::at(input, pattern, "expected stripes")),
+        None => Ok(pattern.to_string()),
+    };
+    Ok(Input {
+        towels: towels.split(", ").map(stripes).collect::<Result<_, _>>()?,
+        designs: designs.lines().map(stripes).collect::<Result<_, _>>()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "\
+r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+";
+
+    #[rstest]
+    #[case("brwrr", 2)]
+    #[case("bggr", 1)]
+    #[case("gbbr", 4)]
+    #[case("rrbgbr", 6)]
+    #[case("ubwu", 0)]
+    #[case("bwurrg", 1)]
+    #[case("brgr", 2)]
+    #[case("bbrgwb", 0)]
+    fn counts_arrangements(#[case] design: &str, #[case] ways: u64) {
+        assert_eq!(parse(EXAMPLE).unwrap().arrangements(design), ways);
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 6);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 16);
+    }
+
+    #[test]
+    fn rejects_unknown_colour() {
+        assert_eq!(
+            parse("r, wx\n\nrw\n"),
+            Err(ParseError::new(1, 5, "expected a stripe colour, found 'x'"))
+        );
+    }
+}
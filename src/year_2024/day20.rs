@@ -0,0 +1,130 @@
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::parallel;
+use crate::search;
+use crate::ParseError;
+
+crate::solver!(2024, 20, Input);
+
+/// Picoseconds a cheat has to save to be counted.
+const SAVING: usize = 100;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+    /// Picoseconds from the start to every position on the track.
+    times: Grid<Option<usize>>,
+    track: Vec<Point>,
+}
+
+pub fn part1(input: &Input) -> usize {
+    count_cheats(input, 2, SAVING)
+}
+
+pub fn part2(input: &Input) -> usize {
+    count_cheats(input, 20, SAVING)
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let map = Grid::parse(input, "a wall, track, start or end", |char| {
+        "#.SE".contains(char).then_some(char)
+    })?;
+    let start = map
+        .find(|tile| *tile == 'S')
+        .ok_or_else(|| ParseError::end(input, "expected the start"))?;
+    if map.find(|tile| *tile == 'E').is_none() {
+        return Err(ParseError::end(input, "expected the end"));
+    }
+    let distances = search::distances(start, |position| {
+        position
+            .neighbours()
+            .filter(|next| map.get(*next).is_some_and(|tile| *tile != '#'))
+            .collect::<Vec<_>>()
+    });
+    let mut times = map.map(|_| None);
+    for (position, time) in &distances {
+        times[*position] = Some(*time);
+    }
+    Ok(Input {
+        times,
+        track: distances.into_keys().collect(),
+    })
+}
+
+/// Number of cheats that pass through walls for at most `duration` picoseconds and save at
+/// least `saving` picoseconds.
+///
+/// A cheat is a jump between two positions on the track, which saves the time between them on the
+/// track except the time of the jump itself.
+fn count_cheats(input: &Input, duration: isize, saving: usize) -> usize {
+    let jumps = (-duration..=duration)
+        .flat_map(|row| {
+            let columns = duration - row.abs();
+            (-columns..=columns).map(move |column| Point::new(row, column))
+        })
+        .collect::<Vec<_>>();
+    parallel::sum(&input.track, |from| {
+        let before = input.times[*from].expect("a position on the track");
+        jumps
+            .iter()
+            .filter(|jump| {
+                let length = jump.manhattan(Point::ORIGIN);
+                input
+                    .times
+                    .get(*from + **jump)
+                    .copied()
+                    .flatten()
+                    .is_some_and(|after| after >= before + length + saving)
+            })
+            .count()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "\
+###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+";
+
+    #[test]
+    fn times_the_track() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(input.track.len(), 85);
+        assert_eq!(input.times[Point::new(7, 5)], Some(84));
+    }
+
+    #[rstest]
+    #[case(2, 44)]
+    #[case(12, 8)]
+    #[case(38, 3)]
+    #[case(64, 1)]
+    #[case(65, 0)]
+    fn solves_part1(#[case] saving: usize, #[case] cheats: usize) {
+        assert_eq!(count_cheats(&parse(EXAMPLE).unwrap(), 2, saving), cheats);
+    }
+
+    #[rstest]
+    #[case(50, 285)]
+    #[case(72, 29)]
+    #[case(74, 7)]
+    #[case(76, 3)]
+    fn solves_part2(#[case] saving: usize, #[case] cheats: usize) {
+        assert_eq!(count_cheats(&parse(EXAMPLE).unwrap(), 20, saving), cheats);
+    }
+}
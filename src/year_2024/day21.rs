@@ -0,0 +1,177 @@
+use crate::geometry::Point;
+use crate::ParseError;
+use std::collections::HashMap;
+
+crate::solver!(2024, 21, Vec<String>);
+
+/// The door's keypad, with a space for the gap the robot arms must not point at.
+const NUMERIC: &str = "789\n456\n123\n 0A";
+const DIRECTIONAL: &str = " ^A\n<v>";
+
+pub fn part1(codes: &[String]) -> u64 {
+    let mut keypads = Keypads::default();
+    codes.iter().map(|code| keypads.complexity(code, 2)).sum()
+}
+
+pub fn part2(codes: &[String]) -> u64 {
+    let mut keypads = Keypads::default();
+    codes.iter().map(|code| keypads.complexity(code, 25)).sum()
+}
+
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    input
+        .lines()
+        .map(|code| match code.as_bytes() {
+            [digits @ .., b'A'] if !digits.is_empty() && digits.iter().all(u8::is_ascii_digit) => {
+                Ok(code.to_string())
+            }
+            _ => Err(ParseError::at(
+                input,
+                code,
+                format!("expected digits followed by 'A', found {:?}", code),
+            )),
+        })
+        .collect()
+}
+
+/// Button presses on the directional keypads, remembered per move and number of robots.
+#[derive(Default)]
+struct Keypads {
+    presses: HashMap<(char, char, usize), u64>,
+}
+
+impl Keypads {
+    /// The numeric part of the code times the number of presses to type it, with `robots`
+    /// robots at directional keypads between the human and the robot at the door.
+    fn complexity(&mut self, code: &str, robots: usize) -> u64 {
+        let number = code[..code.len() - 1]
+            .parse::<u64>()
+            .expect("a numeric code");
+        number * self.sequence(NUMERIC, code, robots + 1)
+    }
+
+    /// Presses to make the arm at a keypad press `keys`, starting and ending at `A`, with `depth`
+    /// directional keypads in front of it.
+    fn sequence(&mut self, layout: &str, keys: &str, depth: usize) -> u64 {
+        let mut from = 'A';
+        keys.chars()
+            .map(|to| self.press(layout, std::mem::replace(&mut from, to), to, depth))
+            .sum()
+    }
+
+    /// Presses to move the arm at a keypad from one key to another and press it.
+    ///
+    /// The arm moves in at most two straight lines, as every turn costs another round of moves on
+    /// the keypads in front of it, and the cheapest of the two orders is found by recursion.
+    fn press(&mut self, layout: &str, from: char, to: char, depth: usize) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let memo = layout == DIRECTIONAL;
+        if let Some(presses) = memo.then(|| self.presses.get(&(from, to, depth))).flatten() {
+            return *presses;
+        }
+        let presses = moves(layout, from, to)
+            .iter()
+            .map(|keys| self.sequence(DIRECTIONAL, keys, depth - 1))
+            .min()
+            .expect("a way to move the arm");
+        if memo {
+            self.presses.insert((from, to, depth), presses);
+        }
+        presses
+    }
+}
+
+fn locate(layout: &str, key: char) -> Point {
+    layout
+        .lines()
+        .enumerate()
+        .find_map(|(row, keys)| {
+            let column = keys.find(key)?;
+            Some(Point::new(row as isize, column as isize))
+        })
+        .expect("a key on the keypad")
+}
+
+/// Directional keys that move the arm from one key to another in at most two straight lines,
+/// avoiding the gap, each followed by `A` to press the key.
+fn moves(layout: &str, from: char, to: char) -> Vec<String> {
+    let (from, to, gap) = (
+        locate(layout, from),
+        locate(layout, to),
+        locate(layout, ' '),
+    );
+    let vertical = if to.row > from.row { "v" } else { "^" }.repeat(from.row.abs_diff(to.row));
+    let horizontal =
+        if to.column > from.column { ">" } else { "<" }.repeat(from.column.abs_diff(to.column));
+    let mut moves = Vec::new();
+    if Point::new(from.row, to.column) != gap {
+        moves.push(format!("{}{}A", horizontal, vertical));
+    }
+    if Point::new(to.row, from.column) != gap {
+        moves.push(format!("{}{}A", vertical, horizontal));
+    }
+    moves.dedup();
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "\
+029A
+980A
+179A
+456A
+379A
+";
+
+    #[rstest]
+    #[case('A', '0', &["<A"])]
+    #[case('A', '1', &["^<<A"])]
+    #[case('0', '7', &["^^^<A"])]
+    #[case('1', '9', &[">>^^A", "^^>>A"])]
+    fn moves_around_the_gap(#[case] from: char, #[case] to: char, #[case] expected: &[&str]) {
+        assert_eq!(moves(NUMERIC, from, to), expected);
+    }
+
+    #[rstest]
+    #[case("029A", 68)]
+    #[case("980A", 60)]
+    #[case("179A", 68)]
+    #[case("456A", 64)]
+    #[case("379A", 64)]
+    fn counts_presses(#[case] code: &str, #[case] presses: u64) {
+        assert_eq!(Keypads::default().sequence(NUMERIC, code, 3), presses);
+    }
+
+    #[test]
+    fn types_through_one_keypad() {
+        assert_eq!(Keypads::default().sequence(NUMERIC, "029A", 1), 12);
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 126384);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 154115708116294);
+    }
+
+    #[test]
+    fn rejects_invalid_code() {
+        assert_eq!(
+            parse("029A\n98A0\n"),
+            Err(ParseError::new(
+                2,
+                1,
+                "expected digits followed by 'A', found \"98A0\""
+            ))
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::parsing::parse_at;
+use crate::ParseError;
+use itertools::iterate;
+
+crate::solver!(2024, 22, Vec<u64>);
+
+/// Secret numbers each buyer generates in a day.
+const SECRETS: usize = 2000;
+/// Price changes range from -9 to 9.
+const CHANGES: usize = 19;
+
+pub fn part1(secrets: &[u64]) -> u64 {
+    secrets
+        .iter()
+        .map(|secret| {
+            iterate(*secret, next)
+                .nth(SECRETS)
+                .expect("endless secrets")
+        })
+        .sum()
+}
+
+pub fn part2(secrets: &[u64]) -> u32 {
+    most_bananas(secrets)
+}
+
+pub fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    input
+        .lines()
+        .map(|line| parse_at(input, line, "a secret number"))
+        .collect()
+}
+
+fn next(secret: &u64) -> u64 {
+    const PRUNE: u64 = 1 << 24;
+    let secret = (secret << 6 ^ secret) % PRUNE;
+    let secret = (secret >> 5 ^ secret) % PRUNE;
+    (secret << 11 ^ secret) % PRUNE
+}
+
+/// Most bananas for a sequence of four price changes, after which the monkey sells to every buyer
+/// the first time their prices change that way.
+///
+/// The bananas are added up for all sequences at once, each sequence indexed as a number in base
+/// 19, remembering which buyer last sold for it.
+fn most_bananas(secrets: &[u64]) -> u32 {
+    let sequences = CHANGES.pow(4);
+    let mut bananas = vec![0; sequences];
+    let mut last_buyer = vec![usize::MAX; sequences];
+    for (buyer, secret) in secrets.iter().enumerate() {
+        let prices = iterate(*secret, next)
+            .take(SECRETS + 1)
+            .map(|secret| (secret % 10) as usize)
+            .collect::<Vec<_>>();
+        let mut sequence = 0;
+        for (index, pair) in prices.windows(2).enumerate() {
+            let change = pair[1] + 9 - pair[0];
+            sequence = (sequence * CHANGES + change) % sequences;
+            if index >= 3 && last_buyer[sequence] != buyer {
+                last_buyer[sequence] = buyer;
+                bananas[sequence] += pair[1] as u32;
+            }
+        }
+    }
+    bananas.into_iter().max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_secrets() {
+        assert_eq!(
+            iterate(123, next).skip(1).take(10).collect::<Vec<_>>(),
+            [
+                15887950, 16495136, 527345, 704524, 1553684, 12683156, 11100544, 12249484, 7753432,
+                5908254
+            ]
+        );
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(part1(&parse("1\n10\n100\n2024\n").unwrap()), 37327623);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(part2(&parse("1\n2\n3\n2024\n").unwrap()), 23);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::parsing::split_once_at;
+use crate::ParseError;
+use itertools::Itertools;
+use std::collections::{BTreeSet, HashMap};
+
+crate::solver!(2024, 23, Network);
+
+/// The computers each computer is connected to.
+pub type Network = HashMap<String, BTreeSet<String>>;
+
+pub fn part1(network: &Network) -> usize {
+    triangles(network)
+        .filter(|triangle| triangle.iter().any(|computer| computer.starts_with('t')))
+        .count()
+}
+
+pub fn part2(network: &Network) -> String {
+    largest_clique(network).into_iter().join(",")
+}
+
+pub fn parse(input: &str) -> Result<Network, ParseError> {
+    let mut network = Network::new();
+    for line in input.lines() {
+        let (a, b) = split_once_at(input, line, "-")?;
+        for (from, to) in [(a, b), (b, a)] {
+            network
+                .entry(from.to_string())
+                .or_default()
+                .insert(to.to_string());
+        }
+    }
+    Ok(network)
+}
+
+/// Sets of three computers that are all connected to each other, each listed once in order.
+fn triangles(network: &Network) -> impl Iterator<Item = [&str; 3]> {
+    network.iter().flat_map(move |(a, neighbours)| {
+        neighbours
+            .range::<String, _>((a.clone())..)
+            .tuple_combinations()
+            .filter(|(b, c)| network[*b].contains(*c))
+            .map(move |(b, c)| [a.as_str(), b.as_str(), c.as_str()])
+    })
+}
+
+/// The largest set of computers that are all connected to each other, in order.
+///
+/// Found with the Bron–Kerbosch algorithm, which grows cliques one computer at a time and skips
+/// the neighbours of a pivot, as any clique with them can also be grown from the pivot.
+fn largest_clique(network: &Network) -> BTreeSet<&str> {
+    fn grow<'a>(
+        network: &'a Network,
+        clique: &mut Vec<&'a str>,
+        mut candidates: BTreeSet<&'a str>,
+        mut excluded: BTreeSet<&'a str>,
+        largest: &mut Vec<&'a str>,
+    ) {
+        if candidates.is_empty() && excluded.is_empty() && clique.len() > largest.len() {
+            largest.clone_from(clique);
+        }
+        let Some(pivot) = candidates.iter().chain(&excluded).next().copied() else {
+            return;
+        };
+        let neighbours = |computer: &str| {
+            network[computer]
+                .iter()
+                .map(String::as_str)
+                .collect::<BTreeSet<_>>()
+        };
+        let pivot_neighbours = neighbours(pivot);
+        for computer in candidates.clone() {
+            if pivot_neighbours.contains(computer) {
+                continue;
+            }
+            let computer_neighbours = neighbours(computer);
+            clique.push(computer);
+            grow(
+                network,
+                clique,
+                &candidates & &computer_neighbours,
+                &excluded & &computer_neighbours,
+                largest,
+            );
+            clique.pop();
+            candidates.remove(computer);
+            excluded.insert(computer);
+        }
+    }
+
+    let mut largest = Vec::new();
+    let computers = network.keys().map(String::as_str).collect();
+    grow(
+        network,
+        &mut Vec::new(),
+        computers,
+        BTreeSet::new(),
+        &mut largest,
+    );
+    largest.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn
+";
+
+    #[test]
+    fn finds_triangles() {
+        let network = parse(EXAMPLE).unwrap();
+        let triangles = triangles(&network).collect::<BTreeSet<_>>();
+        assert_eq!(triangles.len(), 12);
+        assert!(triangles.contains(&["co", "de", "ka"]));
+        assert!(triangles.contains(&["tb", "vc", "wq"]));
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 7);
+    }
+
+    #[test]
+    fn solves_part2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), "co,de,ka,ta");
+    }
+}
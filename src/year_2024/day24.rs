@@ -0,0 +1,258 @@
+use crate::parsing::split_once_at;
+use crate::ParseError;
+use itertools::Itertools;
+use std::collections::{BTreeSet, HashMap};
+
+crate::solver!(2024, 24, Input);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Operation {
+    And,
+    Or,
+    Xor,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Gate {
+    inputs: [String; 2],
+    operation: Operation,
+    output: String,
+}
+
+impl Gate {
+    fn reads(&self, wire: &str) -> bool {
+        self.inputs.iter().any(|input| input == wire)
+    }
+
+    /// Whether the gate reads the input bits rather than the outputs of other gates.
+    fn reads_inputs(&self) -> bool {
+        self.inputs
+            .iter()
+            .all(|input| input.starts_with('x') || input.starts_with('y'))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+    initial: HashMap<String, bool>,
+    gates: Vec<Gate>,
+}
+
+impl Input {
+    /// The number on the `z` wires once all signals have arrived.
+    fn output(&self) -> u64 {
+        let drivers = self
+            .gates
+            .iter()
+            .map(|gate| (gate.output.as_str(), gate))
+            .collect::<HashMap<_, _>>();
+        let mut values = self
+            .initial
+            .iter()
+            .map(|(wire, value)| (wire.as_str(), *value))
+            .collect::<HashMap<_, _>>();
+        self.z_wires().rev().fold(0, |number, wire| {
+            number << 1 | u64::from(signal(wire, &drivers, &mut values))
+        })
+    }
+
+    /// The output wires from the lowest bit on.
+    fn z_wires(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.gates
+            .iter()
+            .map(|gate| gate.output.as_str())
+            .filter(|wire| wire.starts_with('z'))
+            .sorted()
+    }
+
+    /// Outputs of gates that are wired differently than in a ripple-carry adder.
+    ///
+    /// Every bit of the adder XORs the input bits into a partial sum, which is XORed with the
+    /// carry into the output bit, while the ANDs of both pairs are ORed into the next carry. The
+    /// lowest bit has no carry in and the highest output is the last carry.
+    fn miswired(&self) -> BTreeSet<&str> {
+        let highest = self.z_wires().next_back().unwrap_or_default();
+        let feeds = |wire: &str, operation: Operation| {
+            self.gates
+                .iter()
+                .any(|gate| gate.operation == operation && gate.reads(wire))
+        };
+        self.gates
+            .iter()
+            .filter(|gate| {
+                let output = gate.output.as_str();
+                let first_bit = gate.reads("x00");
+                let sum = output.starts_with('z') && output != highest;
+                (sum && gate.operation != Operation::Xor)
+                    || (output == highest && gate.operation != Operation::Or)
+                    || match gate.operation {
+                        Operation::Xor if !gate.reads_inputs() => !sum,
+                        Operation::Xor => !first_bit && !feeds(output, Operation::Xor),
+                        Operation::And => !first_bit && !feeds(output, Operation::Or),
+                        Operation::Or => false,
+                    }
+            })
+            .map(|gate| gate.output.as_str())
+            .collect()
+    }
+}
+
+pub fn part1(input: &Input) -> u64 {
+    input.output()
+}
+
+pub fn part2(input: &Input) -> String {
+    input.miswired().into_iter().join(",")
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let (initial, gates) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::end(input, "expected the gates after an empty line"))?;
+    let initial = initial
+        .lines()
+        .map(|line| {
+            let (wire, value) = split_once_at(input, line, ": ")?;
+            match value {
+                "0" => Ok((wire.to_string(), false)),
+                "1" => Ok((wire.to_string(), true)),
+                _ => Err(ParseError::at(
+                    input,
+                    value,
+                    format!("expected 0 or 1, found {:?}", value),
+                )),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    let gates = gates
+        .lines()
+        .map(|line| {
+            let (operation, output) = split_once_at(input, line, " -> ")?;
+            let [a, operation, b] = operation.split(' ').collect::<Vec<_>>()[..] else {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("expected a gate, found {:?}", line),
+                ));
+            };
+            let operation = match operation {
+                "AND" => Operation::And,
+                "OR" => Operation::Or,
+                "XOR" => Operation::Xor,
+                _ => {
+                    return Err(ParseError::at(
+                        input,
+                        operation,
+                        format!("expected AND, OR or XOR, found {:?}", operation),
+                    ))
+                }
+            };
+            Ok(Gate {
+                inputs: [a.to_string(), b.to_string()],
+                operation,
+                output: output.to_string(),
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Input { initial, gates })
+}
+
+/// The signal on a wire, working out the signals of the gates before it as needed.
+fn signal<'a>(
+    wire: &'a str,
+    drivers: &HashMap<&str, &'a Gate>,
+    values: &mut HashMap<&'a str, bool>,
+) -> bool {
+    if let Some(value) = values.get(wire) {
+        return *value;
+    }
+    let gate = drivers
+        .get(wire)
+        .unwrap_or_else(|| panic!("a signal or gate for wire {}", wire));
+    let [a, b] = [0, 1].map(|index| signal(&gate.inputs[index], drivers, values));
+    let value = match gate.operation {
+        Operation::And => a & b,
+        Operation::Or => a | b,
+        Operation::Xor => a ^ b,
+    };
+    values.insert(wire, value);
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+x00: 1
+x01: 1
+x02: 1
+y00: 0
+y01: 1
+y02: 0
+
+x00 AND y00 -> z00
+x01 XOR y01 -> z01
+x02 OR y02 -> z02
+";
+
+    /// A 3-bit ripple-carry adder computing 5 + 3.
+    const ADDER: &str = "\
+x00: 1
+x01: 0
+x02: 1
+y00: 1
+y01: 1
+y02: 0
+
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> s01
+s01 XOR c00 -> z01
+x01 AND y01 -> a01
+s01 AND c00 -> b01
+a01 OR b01 -> c01
+x02 XOR y02 -> s02
+s02 XOR c01 -> z02
+x02 AND y02 -> a02
+s02 AND c01 -> b02
+a02 OR b02 -> z03
+";
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 4);
+    }
+
+    #[test]
+    fn adds_numbers() {
+        assert_eq!(part1(&parse(ADDER).unwrap()), 8);
+    }
+
+    #[test]
+    fn accepts_correct_adder() {
+        assert_eq!(part2(&parse(ADDER).unwrap()), "");
+    }
+
+    #[test]
+    fn finds_swapped_outputs() {
+        let swapped = ADDER
+            .replace("s01 XOR c00 -> z01", "s01 XOR c00 -> a01")
+            .replace("x01 AND y01 -> a01", "x01 AND y01 -> z01")
+            .replace("s02 XOR c01 -> z02", "s02 XOR c01 -> b02")
+            .replace("s02 AND c01 -> b02", "s02 AND c01 -> z02");
+        assert_eq!(part2(&parse(&swapped).unwrap()), "a01,b02,z01,z02");
+    }
+
+    #[test]
+    fn rejects_unknown_gate() {
+        assert_eq!(
+            parse("x00: 1\n\nx00 NAND y00 -> z00\n"),
+            Err(ParseError::new(
+                3,
+                5,
+                "expected AND, OR or XOR, found \"NAND\""
+            ))
+        );
+    }
+}
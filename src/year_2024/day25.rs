@@ -0,0 +1,160 @@
+use crate::grid::Grid;
+use crate::ParseError;
+
+crate::solver!(2024, 25, Input, parts = 1);
+
+/// Rows of a schematic, including the full top or bottom row.
+const HEIGHT: usize = 7;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Input {
+    /// Heights of the pins of each lock, from the top.
+    locks: Vec<Vec<usize>>,
+    /// Heights of the teeth of each key, from the bottom.
+    keys: Vec<Vec<usize>>,
+}
+
+pub fn part1(input: &Input) -> usize {
+    input
+        .locks
+        .iter()
+        .flat_map(|lock| input.keys.iter().map(move |key| (lock, key)))
+        .filter(|(lock, key)| fits(lock, key))
+        .count()
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let mut parsed = Input::default();
+    for schematic in input.split("\n\n") {
+        let grid = Grid::parse(schematic, "'#' or '.'", |char| match char {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .map_err(|error| {
+            let start = ParseError::at(input, schematic, "");
+            ParseError::new(start.line + error.line - 1, error.column, error.message)
+        })?;
+        if grid.height() != HEIGHT {
+            return Err(ParseError::at(
+                input,
+                schematic,
+                format!("expected {} rows, found {}", HEIGHT, grid.height()),
+            ));
+        }
+        let heights = (0..grid.width())
+            .map(|column| {
+                grid.column(column)
+                    .filter(|filled| **filled)
+                    .count()
+                    .saturating_sub(1)
+            })
+            .collect();
+        let lock = grid
+            .rows()
+            .next()
+            .is_some_and(|top| top.iter().all(|filled| *filled));
+        if lock {
+            parsed.locks.push(heights);
+        } else {
+            parsed.keys.push(heights);
+        }
+    }
+    Ok(parsed)
+}
+
+/// Whether no pin and tooth overlap in any column.
+fn fits(lock: &[usize], key: &[usize]) -> bool {
+    lock.iter()
+        .zip(key)
+        .all(|(pin, tooth)| pin + tooth <= HEIGHT - 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####
+";
+
+    #[test]
+    fn measures_heights() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(input.locks, [vec![0, 5, 3, 4, 3], vec![1, 2, 0, 5, 3]]);
+        assert_eq!(
+            input.keys,
+            [
+                vec![5, 0, 2, 1, 3],
+                vec![4, 3, 4, 0, 2],
+                vec![3, 0, 2, 0, 1]
+            ]
+        );
+    }
+
+    #[test]
+    fn solves_part1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 3);
+    }
+
+    #[test]
+    fn has_only_one_part() {
+        use crate::Solver;
+        assert_eq!(Day::PARTS, 1);
+        assert_eq!(Day::part2(&Input::default()).to_string(), "-");
+    }
+
+    #[test]
+    fn reports_position_in_input() {
+        assert_eq!(
+            parse(&EXAMPLE.replacen("#.###", "#.#x#", 1)),
+            Err(ParseError::new(22, 4, "expected '#' or '.', found 'x'"))
+        );
+    }
+
+    #[test]
+    fn rejects_short_schematic() {
+        assert_eq!(
+            parse("#####\n.####\n\n.....\n"),
+            Err(ParseError::new(1, 1, "expected 7 rows, found 2"))
+        );
+    }
+}